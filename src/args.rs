@@ -1,4 +1,6 @@
-use clap::{arg, Parser};
+use std::{net::SocketAddr, time::Duration};
+
+use clap::{arg, Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 pub struct BalanceArgs {
@@ -20,10 +22,90 @@ pub struct BenchmarkArgs {
         default_value = "1"
     )]
     pub cores: u64,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        help = "How long to hash in each run",
+        default_value = "30"
+    )]
+    pub duration: u64,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        help = "How long to hash before measuring in each run",
+        default_value = "2"
+    )]
+    pub warmup: u64,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "How many times to repeat the benchmark",
+        default_value = "1"
+    )]
+    pub runs: u64,
+
+    #[arg(
+        long,
+        help = "Benchmark every thread count from 1 to the number of available cores and save the best for mining"
+    )]
+    pub sweep: bool,
+
+    #[arg(
+        long,
+        help = "Also measure each thread count without core pinning",
+        requires = "sweep"
+    )]
+    pub sweep_unpinned: bool,
+
+    #[arg(
+        long,
+        help = "Use only one logical core per physical core",
+        requires = "sweep"
+    )]
+    pub exclude_smt: bool,
+
+    #[arg(
+        long,
+        help = "Hash fixed challenges and nonces and check the digests against known-good values"
+    )]
+    pub verify: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Expected digests to verify against, as written by --record-vectors",
+        requires = "verify"
+    )]
+    pub vectors: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write this build's digests for the fixed challenges and nonces to a file"
+    )]
+    pub record_vectors: Option<String>,
 }
 
 #[derive(Parser, Debug)]
-pub struct BussesArgs {}
+pub struct BussesArgs {
+    #[arg(
+        long,
+        help = "Keep refreshing a live table with depletion rates and the treasury balance"
+    )]
+    pub watch: bool,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        help = "How often to refresh in watch mode",
+        default_value = "5",
+        requires = "watch"
+    )]
+    pub interval: u64,
+}
 
 #[derive(Parser, Debug)]
 pub struct ClaimArgs {
@@ -53,16 +135,108 @@ pub struct ConfigArgs {}
 pub struct InitializeArgs {}
 
 #[derive(Parser, Debug)]
-pub struct MineArgs {
+pub struct EstimateArgs {
     #[arg(
         long,
         short,
         value_name = "CORES_COUNT",
-        help = "The number of CPU cores to allocate to mining",
+        help = "The number of CPU cores to estimate earnings for",
         default_value = "1"
     )]
     pub cores: u64,
 
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        help = "How long to benchmark if no cached result exists for this core count",
+        default_value = "10"
+    )]
+    pub duration: u64,
+
+    #[arg(long, help = "Rerun the benchmark instead of using the cached result")]
+    pub refresh: bool,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Authority address whose stake multiplier to apply. Defaults to your keypair."
+    )]
+    pub address: Option<String>,
+
+    #[arg(
+        long,
+        short,
+        value_name = "SECONDS",
+        help = "The number seconds before the deadline to stop mining and start submitting",
+        default_value = "5"
+    )]
+    pub buffer_time: u64,
+}
+
+#[derive(Parser, Debug)]
+pub struct InspectArgs {
+    #[arg(value_name = "ADDRESS", help = "The address of the account to inspect")]
+    pub address: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct LeaderboardArgs {
+    #[arg(
+        long,
+        value_name = "FIELD",
+        help = "The proof field to rank by",
+        value_enum,
+        default_value = "balance"
+    )]
+    pub sort: LeaderboardSort,
+
+    #[arg(
+        long,
+        short,
+        value_name = "COUNT",
+        help = "The number of top proofs to list",
+        default_value = "20"
+    )]
+    pub limit: usize,
+
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        help = "Count a miner as active if it submitted a hash within this many minutes",
+        default_value = "60"
+    )]
+    pub active_minutes: u64,
+
+    #[arg(
+        long = "authority",
+        value_name = "ADDRESS",
+        help = "Authorities to highlight: a comma separated list. Defaults to your keypair, if one is configured.",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub authorities: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LeaderboardSort {
+    /// Staked balance
+    Balance,
+    /// Lifetime rewards
+    Rewards,
+    /// Lifetime hashes
+    Hashes,
+}
+
+#[derive(Parser, Debug)]
+pub struct MineArgs {
+    #[arg(
+        long,
+        short,
+        value_name = "CORES_COUNT",
+        help = "The number of CPU cores to allocate to mining. Defaults to the count recommended by `benchmark --sweep`, or 1."
+    )]
+    pub cores: Option<u64>,
+
     #[arg(
         long,
         short,
@@ -95,20 +269,148 @@ pub struct MineArgs {
         default_value = "60"
     )]
     pub time_limit: u64,
+
+    #[arg(
+        long,
+        value_name = "KEYPAIRS",
+        help = "Keypairs to mine with from one process: a directory of keypair files or a comma separated list. Defaults to --keypair.",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub keypairs: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "SCHEDULE",
+        help = "How to schedule challenges when mining with multiple keypairs",
+        value_enum,
+        default_value = "parallel"
+    )]
+    pub schedule: MineSchedule,
+
+    #[arg(
+        long,
+        help = "Submit solutions on-chain instead of through the pool, batching every keypair's mine instruction through the fee payer"
+    )]
+    pub solo: bool,
+
+    #[arg(
+        long,
+        value_name = "AMOUNT",
+        help = "Automatically claim rewards once a proof's balance reaches this many ORE"
+    )]
+    pub auto_claim_threshold: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "WALLET_ADDRESS",
+        help = "Wallet to receive automatically claimed tokens. Defaults to the authority.",
+        requires = "auto_claim_threshold"
+    )]
+    pub auto_claim_to: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        help = "Automatically stake the wallet's ORE at this interval, e.g. 30m, 24h or 7d",
+        value_parser = parse_duration
+    )]
+    pub auto_stake_interval: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Serve Prometheus metrics at this address, e.g. 0.0.0.0:9100"
+    )]
+    pub metrics_addr: Option<SocketAddr>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Serve a JSON status API with pause and resume controls at this address, e.g. 127.0.0.1:8080"
+    )]
+    pub status_addr: Option<SocketAddr>,
+
+    #[arg(long, help = "Show a full-screen dashboard instead of log lines")]
+    pub tui: bool,
+
+    #[arg(
+        long = "webhook-url",
+        value_name = "URL",
+        help = "POST a JSON payload to this URL on mining events. Repeat for several URLs."
+    )]
+    pub webhook_urls: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "DIFFICULTY",
+        help = "Notify webhooks when a round sets a new session best difficulty at or above this value",
+        requires = "webhook_urls"
+    )]
+    pub webhook_difficulty: Option<u32>,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Notify webhooks after this many consecutive transaction failures",
+        default_value = "3",
+        requires = "webhook_urls"
+    )]
+    pub webhook_failure_streak: u64,
+
+    #[arg(
+        long,
+        value_name = "ACTION",
+        help = "What to do when the fee payer cannot cover submissions",
+        value_enum,
+        default_value = "pause"
+    )]
+    pub low_balance: LowBalanceAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MineSchedule {
+    /// Hash each proof's challenge in turn with every core
+    Sequential,
+    /// Split the cores across proofs, earliest cutoff first
+    Parallel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LowBalanceAction {
+    /// Stop hashing and poll the balance until it is topped up
+    Pause,
+    /// Stop mining and exit
+    Exit,
+    /// Keep hashing and hold the best solution per proof until the balance is topped up
+    Queue,
 }
 
 #[derive(Parser, Debug)]
-pub struct ProofArgs {
+pub struct OpenArgs {
     #[arg(
-        index = 0,
-        value_name = "ADDRESS",
-        help = "The address of the proof to fetch"
+        long,
+        value_name = "MINER_ADDRESS",
+        help = "Key allowed to submit hashes for this proof. Defaults to the authority."
     )]
+    pub miner: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ProofArgs {
+    #[arg(value_name = "ADDRESS", help = "The address of the proof to fetch")]
     pub address: Option<String>,
 }
 
 #[derive(Parser, Debug)]
-pub struct RewardsArgs {}
+pub struct RewardsArgs {
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Authority address to show the effective stake multiplier for"
+    )]
+    pub address: Option<String>,
+}
 
 #[derive(Parser, Debug)]
 pub struct StakeArgs {
@@ -125,6 +427,22 @@ pub struct StakeArgs {
         help = "Token account to send Ore from."
     )]
     pub sender: Option<String>,
+
+    #[arg(
+        long,
+        help = "Project the stake multiplier and reward uplift for the amount without staking"
+    )]
+    pub plan: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct SubmitSignedArgs {
+    #[arg(
+        value_name = "TRANSACTION",
+        help = "Base58 or base64 encoded partially signed transactions to combine and submit",
+        required = true
+    )]
+    pub transactions: Vec<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -136,3 +454,26 @@ pub struct UpgradeArgs {
     )]
     pub amount: Option<f64>,
 }
+
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("Invalid duration: {}", s))?;
+    let multiplier: u64 = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        _ => return Err(format!("Invalid duration unit: {}", unit)),
+    };
+    let secs = value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Duration too large: {}", s))?;
+    if secs == 0 {
+        return Err("Duration must be greater than zero".to_string());
+    }
+    Ok(Duration::from_secs(secs))
+}
@@ -0,0 +1,129 @@
+use std::{str::FromStr, time::Instant};
+
+use ore_api::consts::{MINT_ADDRESS, TOKEN_DECIMALS};
+use solana_client::client_error::Result as ClientResult;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use spl_token::amount_to_ui_amount;
+
+use crate::{
+    args::{ClaimArgs, MineArgs, StakeArgs},
+    mining_history::{MiningHistory, TransferRecord},
+    signer::MinerSigner,
+    utils::{amount_f64_to_u64, get_proof_with_authority, proof_pubkey},
+    Miner,
+};
+
+impl Miner {
+    /// Runs the automatic claim and stake policies configured for the mining session
+    /// and logs every transfer to the mining history.
+    pub async fn run_auto_policies(
+        &self,
+        authorities: &[MinerSigner],
+        args: &MineArgs,
+        history: &mut MiningHistory,
+        last_stake_at: &mut Instant,
+    ) {
+        // Claim rewards above the threshold
+        if let Some(threshold) = args.auto_claim_threshold {
+            let threshold = amount_f64_to_u64(threshold);
+            for authority in authorities.iter() {
+                let proof = get_proof_with_authority(&self.rpc_client, authority.pubkey()).await;
+                if proof.balance.lt(&threshold) {
+                    continue;
+                }
+                tracing::info!(
+                    "Automatically claiming {} ORE for {}",
+                    amount_to_ui_amount(proof.balance, TOKEN_DECIMALS),
+                    authority.pubkey()
+                );
+                let claim_args = ClaimArgs {
+                    amount: None,
+                    to: args.auto_claim_to.clone(),
+                };
+                if let Some((amount, beneficiary, result)) =
+                    self.claim_from(authority, claim_args, false).await
+                {
+                    self.record_transfer(history, "claim", authority, amount, beneficiary, result);
+                }
+            }
+        }
+
+        // Stake wallet tokens on an interval
+        if let Some(interval) = args.auto_stake_interval {
+            if last_stake_at.elapsed().lt(&interval) {
+                return;
+            }
+            *last_stake_at = Instant::now();
+            for authority in authorities.iter() {
+                let sender = spl_associated_token_account::get_associated_token_address(
+                    &authority.pubkey(),
+                    &MINT_ADDRESS,
+                );
+                let Ok(Some(token_account)) = self.rpc_client.get_token_account(&sender).await
+                else {
+                    continue;
+                };
+                if u64::from_str(&token_account.token_amount.amount).unwrap_or(0) == 0 {
+                    continue;
+                }
+                tracing::info!(
+                    "Automatically staking {} ORE for {}",
+                    token_account.token_amount.ui_amount_string,
+                    authority.pubkey()
+                );
+                let stake_args = StakeArgs {
+                    amount: None,
+                    sender: None,
+                    plan: false,
+                };
+                if let Some((amount, result)) = self.stake_from(authority, stake_args).await {
+                    let proof_address = proof_pubkey(authority.pubkey());
+                    self.record_transfer(
+                        history,
+                        "stake",
+                        authority,
+                        amount,
+                        proof_address,
+                        result,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Records a transfer in the mining history, logging it first if it failed.
+    fn record_transfer(
+        &self,
+        history: &mut MiningHistory,
+        kind: &str,
+        authority: &MinerSigner,
+        amount: u64,
+        destination: Pubkey,
+        result: ClientResult<Option<Signature>>,
+    ) {
+        let (signature, error) = match result {
+            Ok(Some(signature)) => (Some(signature.to_string()), None),
+            // Simulated and exported transactions did not move any tokens
+            Ok(None) => return,
+            Err(err) => {
+                tracing::warn!(
+                    "Automatic {} failed for {}: {}",
+                    kind,
+                    authority.pubkey(),
+                    err
+                );
+                (None, Some(err.to_string()))
+            }
+        };
+        history.add_transfer(TransferRecord {
+            kind: kind.to_string(),
+            authority: authority.pubkey().to_string(),
+            amount,
+            destination: destination.to_string(),
+            signature,
+            error,
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+    }
+}
@@ -1,13 +1,35 @@
 use std::str::FromStr;
 
+use ore_api::consts::TOKEN_DECIMALS;
+use serde::Serialize;
 use solana_program::pubkey::Pubkey;
-use solana_sdk::signature::Signer;
+use spl_token::amount_to_ui_amount;
 
-use crate::{
-    args::BalanceArgs,
-    utils::{amount_u64_to_string, get_proof_with_authority},
-    Miner,
-};
+use crate::{args::BalanceArgs, output::CommandOutput, utils::get_proof_with_authority, Miner};
+
+#[derive(Debug, Serialize)]
+pub struct BalanceOutput {
+    pub address: String,
+    pub balance: f64,
+    pub stake: f64,
+}
+
+impl CommandOutput for BalanceOutput {
+    fn to_text(&self) -> String {
+        format!("Balance: {} ORE\nStake: {} ORE", self.balance, self.stake)
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        (
+            vec!["Address", "Balance (ORE)", "Stake (ORE)"],
+            vec![vec![
+                self.address.clone(),
+                self.balance.to_string(),
+                self.stake.to_string(),
+            ]],
+        )
+    }
+}
 
 impl Miner {
     pub async fn balance(&self, args: BalanceArgs) {
@@ -32,14 +54,14 @@ impl Miner {
             .get_token_account(&token_account_address)
             .await
         {
-            token_account.token_amount.ui_amount_string
+            token_account.token_amount.ui_amount.unwrap_or(0.0)
         } else {
-            "0".to_string()
+            0.0
         };
-        println!(
-            "Balance: {} ORE\nStake: {} ORE",
-            token_balance,
-            amount_u64_to_string(proof.balance)
-        )
+        self.print_output(&BalanceOutput {
+            address: address.to_string(),
+            balance: token_balance,
+            stake: amount_to_ui_amount(proof.balance, TOKEN_DECIMALS),
+        });
     }
 }
@@ -1,80 +1,286 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
 
+use core_affinity::CoreId;
 use drillx::equix;
-use solana_rpc_client::spinner;
+use serde::{Deserialize, Serialize};
 
-use crate::{args::BenchmarkArgs, Miner};
+use crate::{args::BenchmarkArgs, output::CommandOutput, progress::Progress, Miner};
 
-const TEST_DURATION: i64 = 30;
+const BENCHMARK_CACHE: &str = "benchmark.json";
+
+/// The last benchmark result, reused by commands that need a hashrate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkCache {
+    pub cores: u64,
+    pub hashrate: u64,
+    pub timestamp: i64,
+}
+
+impl BenchmarkCache {
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(BENCHMARK_CACHE).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) {
+        if let Ok(content) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(BENCHMARK_CACHE, content);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadResult {
+    pub core: usize,
+    pub hashes: u64,
+    pub hashrate: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkRun {
+    pub hashrate: u64,
+    pub threads: Vec<ThreadResult>,
+    /// Number of hashes found at each difficulty, indexed by difficulty.
+    #[serde(skip)]
+    pub histogram: Vec<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistogramBucket {
+    pub difficulty: usize,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchmarkOutput {
+    pub cores: u64,
+    pub duration: u64,
+    pub warmup: u64,
+    pub runs: Vec<BenchmarkRun>,
+    pub min_hashrate: u64,
+    pub mean_hashrate: u64,
+    pub max_hashrate: u64,
+    pub histogram: Vec<HistogramBucket>,
+}
+
+impl CommandOutput for BenchmarkOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![];
+        for (i, run) in self.runs.iter().enumerate() {
+            if self.runs.len() > 1 {
+                lines.push(format!("Run {}: {} H/sec", i + 1, run.hashrate));
+            }
+            for thread in run.threads.iter() {
+                lines.push(format!("  Core {}: {} H/sec", thread.core, thread.hashrate));
+            }
+        }
+        if self.runs.len() > 1 {
+            lines.push(format!(
+                "Hashpower: {} H/sec (min {} | max {})",
+                self.mean_hashrate, self.min_hashrate, self.max_hashrate
+            ));
+        } else {
+            lines.push(format!("Hashpower: {} H/sec", self.mean_hashrate));
+        }
+        lines.push("Difficulty histogram:".to_string());
+        for bucket in self.histogram.iter() {
+            lines.push(format!("  {}: {}", bucket.difficulty, bucket.count));
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let mut rows = vec![];
+        for (i, run) in self.runs.iter().enumerate() {
+            for thread in run.threads.iter() {
+                rows.push(vec![
+                    (i + 1).to_string(),
+                    thread.core.to_string(),
+                    thread.hashes.to_string(),
+                    thread.hashrate.to_string(),
+                ]);
+            }
+            rows.push(vec![
+                (i + 1).to_string(),
+                "all".to_string(),
+                run.threads
+                    .iter()
+                    .map(|t| t.hashes)
+                    .sum::<u64>()
+                    .to_string(),
+                run.hashrate.to_string(),
+            ]);
+        }
+        (vec!["Run", "Core", "Hashes", "H/sec"], rows)
+    }
+}
 
 impl Miner {
     pub async fn benchmark(&self, args: BenchmarkArgs) {
+        // Check drillx output instead
+        if args.verify || args.record_vectors.is_some() {
+            self.benchmark_verify(args).await;
+            return;
+        }
+
+        // Sweep thread counts instead
+        if args.sweep {
+            self.benchmark_sweep(args).await;
+            return;
+        }
+
         // Check num threads
         self.check_num_cores(args.cores);
 
-        // Dispatch job to each thread
+        // Run the benchmark
+        let progress_bar = Progress::new();
+        let mut runs = vec![];
+        for i in 0..args.runs.max(1) {
+            progress_bar.set_message(format!(
+                "Benchmarking run {} of {}. This will take {} sec...",
+                i + 1,
+                args.runs.max(1),
+                args.warmup + args.duration
+            ));
+            runs.push(Self::run_benchmark(
+                args.cores,
+                Duration::from_secs(args.duration),
+                Duration::from_secs(args.warmup),
+            ));
+        }
+
+        // Aggregate runs
+        let hashrates: Vec<u64> = runs.iter().map(|run| run.hashrate).collect();
+        let min_hashrate = hashrates.iter().copied().min().unwrap_or(0);
+        let max_hashrate = hashrates.iter().copied().max().unwrap_or(0);
+        let mean_hashrate = hashrates.iter().sum::<u64>() / hashrates.len().max(1) as u64;
+        let mut histogram = vec![0u64; 64];
+        for run in runs.iter() {
+            for (difficulty, count) in run.histogram.iter().enumerate() {
+                histogram[difficulty] += count;
+            }
+        }
+        progress_bar.finish_with_message(format!("Hashpower: {} H/sec", mean_hashrate));
+
+        // Save for estimates and print
+        BenchmarkCache {
+            cores: args.cores,
+            hashrate: mean_hashrate,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+        .save();
+        let output = BenchmarkOutput {
+            cores: args.cores,
+            duration: args.duration,
+            warmup: args.warmup,
+            runs,
+            min_hashrate,
+            mean_hashrate,
+            max_hashrate,
+            histogram: histogram
+                .into_iter()
+                .enumerate()
+                .filter(|(_, count)| *count > 0)
+                .map(|(difficulty, count)| HistogramBucket { difficulty, count })
+                .collect(),
+        };
+        self.print_output(&output);
+    }
+
+    /// Hashes on the given number of cores for `duration` seconds and returns H/s.
+    pub fn measure_hashrate(cores: u64, duration: i64) -> u64 {
+        Self::run_benchmark(
+            cores,
+            Duration::from_secs(duration.max(1) as u64),
+            Duration::ZERO,
+        )
+        .hashrate
+    }
+
+    /// Hashes on each of the first `cores` cores, pinned once per thread, for `duration`
+    /// after an uncounted `warmup`.
+    pub fn run_benchmark(cores: u64, duration: Duration, warmup: Duration) -> BenchmarkRun {
+        let core_ids = core_affinity::get_core_ids()
+            .unwrap()
+            .into_iter()
+            .filter(|i| (i.id as u64).lt(&cores))
+            .collect();
+        Self::run_benchmark_on(core_ids, true, duration, warmup)
+    }
+
+    /// Hashes with one thread per core id, optionally pinning each thread to its core.
+    pub fn run_benchmark_on(
+        core_ids: Vec<CoreId>,
+        pin: bool,
+        duration: Duration,
+        warmup: Duration,
+    ) -> BenchmarkRun {
         let challenge = [0; 32];
-        let progress_bar = Arc::new(spinner::new_progress_bar());
-        progress_bar.set_message(format!(
-            "Benchmarking. This will take {} sec...",
-            TEST_DURATION
-        ));
-        let core_ids = core_affinity::get_core_ids().unwrap();
+        let num_threads = core_ids.len().max(1) as u64;
         let handles: Vec<_> = core_ids
             .into_iter()
-            .map(|i| {
-                std::thread::spawn({
-                    move || {
-                        let timer = Instant::now();
-                        let first_nonce = u64::MAX
-                            .saturating_div(args.cores)
-                            .saturating_mul(i.id as u64);
-                        let mut nonce = first_nonce;
-                        let mut memory = equix::SolverMemory::new();
-                        loop {
-                            // Return if core should not be used
-                            if (i.id as u64).ge(&args.cores) {
-                                return 0;
-                            }
-
-                            // Pin to core
-                            let _ = core_affinity::set_for_current(i);
-
-                            // Create hash
-                            let _hx = drillx::hash_with_memory(
-                                &mut memory,
-                                &challenge,
-                                &nonce.to_le_bytes(),
-                            );
-
-                            // Increment nonce
-                            nonce += 1;
-
-                            // Exit if time has elapsed
-                            if (timer.elapsed().as_secs() as i64).ge(&TEST_DURATION) {
-                                break;
-                            }
-                        }
+            .enumerate()
+            .map(|(j, i)| {
+                std::thread::spawn(move || {
+                    // Pin to core
+                    if pin {
+                        let _ = core_affinity::set_for_current(i);
+                    }
+                    let mut memory = equix::SolverMemory::new();
+                    let mut nonce = u64::MAX
+                        .saturating_div(num_threads)
+                        .saturating_mul(j as u64);
+
+                    // Warm up caches and clocks
+                    let timer = Instant::now();
+                    while timer.elapsed().lt(&warmup) {
+                        let _hx =
+                            drillx::hash_with_memory(&mut memory, &challenge, &nonce.to_le_bytes());
+                        nonce += 1;
+                    }
 
-                        // Return hash count
-                        nonce - first_nonce
+                    // Hash until time has elapsed
+                    let mut hashes = 0u64;
+                    let mut histogram = vec![0u64; 64];
+                    let timer = Instant::now();
+                    while timer.elapsed().lt(&duration) {
+                        if let Ok(hx) =
+                            drillx::hash_with_memory(&mut memory, &challenge, &nonce.to_le_bytes())
+                        {
+                            histogram[(hx.difficulty() as usize).min(63)] += 1;
+                        }
+                        hashes += 1;
+                        nonce += 1;
                     }
+                    let secs = timer.elapsed().as_secs_f64().max(f64::EPSILON);
+                    let result = ThreadResult {
+                        core: i.id,
+                        hashes,
+                        hashrate: (hashes as f64 / secs) as u64,
+                    };
+                    (result, histogram)
                 })
             })
             .collect();
 
-        // Join handles and return best nonce
-        let mut total_nonces = 0;
+        // Join handles and aggregate
+        let mut threads = vec![];
+        let mut histogram = vec![0u64; 64];
         for h in handles {
-            if let Ok(count) = h.join() {
-                total_nonces += count;
+            if let Ok((result, thread_histogram)) = h.join() {
+                for (difficulty, count) in thread_histogram.into_iter().enumerate() {
+                    histogram[difficulty] += count;
+                }
+                threads.push(result);
             }
         }
-
-        // Update log
-        progress_bar.finish_with_message(format!(
-            "Hashpower: {} H/sec",
-            total_nonces.saturating_div(TEST_DURATION as u64),
-        ));
+        BenchmarkRun {
+            hashrate: threads.iter().map(|t| t.hashrate).sum(),
+            threads,
+            histogram,
+        }
     }
 }
@@ -0,0 +1,207 @@
+use std::{collections::HashSet, fs, time::Duration};
+
+use core_affinity::CoreId;
+use serde::{Deserialize, Serialize};
+
+use crate::{args::BenchmarkArgs, output::CommandOutput, progress::Progress, Miner};
+
+const SWEEP_CACHE: &str = "benchmark_sweep.json";
+
+/// The last sweep recommendation, used by `mine` as its default core count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepCache {
+    pub recommended_cores: u64,
+    pub hashrate: u64,
+    pub timestamp: i64,
+}
+
+impl SweepCache {
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(SWEEP_CACHE).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) {
+        if let Ok(content) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(SWEEP_CACHE, content);
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SweepResult {
+    pub threads: u64,
+    pub pinned: bool,
+    pub hashrate: u64,
+    pub hashrate_per_core: u64,
+    /// Whether the threads ran pinned to core ids `0..threads`, as `mine` runs them.
+    #[serde(skip)]
+    pub matches_mine: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SweepOutput {
+    pub duration: u64,
+    pub warmup: u64,
+    pub smt_excluded: bool,
+    pub results: Vec<SweepResult>,
+    pub best_threads: u64,
+    pub best_hashrate: u64,
+    pub best_per_core_threads: u64,
+    pub best_per_core_hashrate: u64,
+    pub recommended_cores: Option<u64>,
+}
+
+impl CommandOutput for SweepOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![];
+        for result in self.results.iter() {
+            lines.push(format!(
+                "{} threads{}: {} H/sec ({} H/sec per core)",
+                result.threads,
+                if result.pinned { "" } else { " (unpinned)" },
+                result.hashrate,
+                result.hashrate_per_core
+            ));
+        }
+        lines.push(format!(
+            "Best hashpower: {} threads at {} H/sec",
+            self.best_threads, self.best_hashrate
+        ));
+        lines.push(format!(
+            "Best efficiency: {} threads at {} H/sec per core",
+            self.best_per_core_threads, self.best_per_core_hashrate
+        ));
+        match self.recommended_cores {
+            Some(cores) => lines.push(format!("Saved {} threads as the default for mining", cores)),
+            None => lines.push(
+                "No result was pinned to the cores mine uses, so no default was saved".to_string(),
+            ),
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let rows = self
+            .results
+            .iter()
+            .map(|result| {
+                vec![
+                    result.threads.to_string(),
+                    result.pinned.to_string(),
+                    result.hashrate.to_string(),
+                    result.hashrate_per_core.to_string(),
+                ]
+            })
+            .collect();
+        (vec!["Threads", "Pinned", "H/sec", "H/sec per core"], rows)
+    }
+}
+
+impl Miner {
+    /// Benchmarks every thread count and saves the one with the highest hashpower.
+    pub async fn benchmark_sweep(&self, args: BenchmarkArgs) {
+        // Select cores
+        let mut core_ids = core_affinity::get_core_ids().unwrap_or_default();
+        if args.exclude_smt {
+            core_ids = physical_core_ids(core_ids);
+        }
+        if core_ids.is_empty() {
+            println!("No cores available to benchmark");
+            return;
+        }
+
+        // Run each thread count, pinned and optionally unpinned
+        let modes: &[bool] = if args.sweep_unpinned {
+            &[true, false]
+        } else {
+            &[true]
+        };
+        let num_steps = core_ids.len() * modes.len();
+        let progress_bar = Progress::new();
+        let mut results = vec![];
+        for threads in 1..=core_ids.len() {
+            for pinned in modes.iter() {
+                progress_bar.set_message(format!(
+                    "Benchmarking step {} of {} ({} threads). This will take {} sec...",
+                    results.len() + 1,
+                    num_steps,
+                    threads,
+                    args.warmup + args.duration
+                ));
+                let run = Self::run_benchmark_on(
+                    core_ids[..threads].to_vec(),
+                    *pinned,
+                    Duration::from_secs(args.duration),
+                    Duration::from_secs(args.warmup),
+                );
+                let sequential = core_ids[..threads]
+                    .iter()
+                    .enumerate()
+                    .all(|(i, core_id)| core_id.id == i);
+                results.push(SweepResult {
+                    threads: threads as u64,
+                    pinned: *pinned,
+                    hashrate: run.hashrate,
+                    hashrate_per_core: run.hashrate / threads as u64,
+                    matches_mine: *pinned && sequential,
+                });
+            }
+        }
+
+        // Pick the best thread counts
+        let best = results.iter().max_by_key(|r| r.hashrate).unwrap();
+        let best_per_core = results.iter().max_by_key(|r| r.hashrate_per_core).unwrap();
+        progress_bar.finish_with_message(format!(
+            "Best hashpower: {} threads at {} H/sec",
+            best.threads, best.hashrate
+        ));
+
+        // Only recommend a thread count measured the way mine pins its threads
+        let recommended = results
+            .iter()
+            .filter(|r| r.matches_mine)
+            .max_by_key(|r| r.hashrate);
+
+        // Save for mining and print
+        if let Some(recommended) = recommended {
+            SweepCache {
+                recommended_cores: recommended.threads,
+                hashrate: recommended.hashrate,
+                timestamp: chrono::Utc::now().timestamp(),
+            }
+            .save();
+        }
+        let output = SweepOutput {
+            duration: args.duration,
+            warmup: args.warmup,
+            smt_excluded: args.exclude_smt,
+            best_threads: best.threads,
+            best_hashrate: best.hashrate,
+            best_per_core_threads: best_per_core.threads,
+            best_per_core_hashrate: best_per_core.hashrate_per_core,
+            recommended_cores: recommended.map(|r| r.threads),
+            results,
+        };
+        self.print_output(&output);
+    }
+}
+
+/// Keeps one logical core per physical core, using the sibling lists Linux exposes in sysfs.
+/// Cores without topology information are kept as they are.
+fn physical_core_ids(core_ids: Vec<CoreId>) -> Vec<CoreId> {
+    let mut seen = HashSet::new();
+    core_ids
+        .into_iter()
+        .filter(|core_id| {
+            let path = format!(
+                "/sys/devices/system/cpu/cpu{}/topology/thread_siblings_list",
+                core_id.id
+            );
+            match fs::read_to_string(path) {
+                Ok(siblings) => seen.insert(siblings.trim().to_string()),
+                Err(_) => true,
+            }
+        })
+        .collect()
+}
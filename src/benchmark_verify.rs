@@ -0,0 +1,446 @@
+use std::fs;
+
+use colored::*;
+use drillx::{equix, Solution};
+use serde::{Deserialize, Serialize};
+
+use crate::{args::BenchmarkArgs, output::CommandOutput, Miner};
+
+/// Challenges hashed by `benchmark --verify`.
+const VECTOR_CHALLENGES: [[u8; 32]; 4] = [[0; 32], [0x01; 32], [0x5a; 32], [0xff; 32]];
+
+/// Nonces hashed against each challenge by `benchmark --verify`.
+const VECTOR_NONCES: [u64; 8] = [0, 1, 2, 3, 255, 65_536, 1 << 32, u64::MAX];
+
+/// Expected digests embedded in the binary, as `(challenge, nonce, digest, hash)` in hex.
+/// A `None` digest means drillx finds no solution for the pair.
+///
+/// Recorded with drillx 2.0.0. Regenerate with `ore benchmark --record-vectors <PATH>` on a
+/// known-good build whenever `VECTOR_CHALLENGES`, `VECTOR_NONCES` or the drillx version changes.
+const EXPECTED_VECTORS: &[(&str, u64, Option<&str>, Option<&str>)] = &[
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        0,
+        Some("b45a828ae35b6ec80b4a898cc60bf0d5"),
+        Some("c1ca5f77bfe25845b1a08450066c5b946fdd05f9e95b18ec810f398cac4a04da"),
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        1,
+        Some("8390c9ad882ce6e6154579baa27347fc"),
+        Some("1e651348babc16b1f024763a4b1792e348421fa3ae129f40de82be6483a2fac6"),
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        2,
+        Some("7a0f83d3a18ff4decd22687344838eff"),
+        Some("b50b7f25a24972adfde27457e22e79c9003a980a49bc2dcce2368a5309853ef1"),
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        3,
+        Some("890dbe3d2222b6a4e10c3e92e59316b9"),
+        Some("30f533a0f39bf4cbfb7134b4c59ec224761f5e722f55dd6e3ac1253f061bbad6"),
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        255,
+        Some("1f31bac73c711cc9cd11b544bbe1ccf7"),
+        Some("02d380b8a9d3ff57ac3cb6aa98d2b89687f954d5e6b012f6dd7e9fba7a8caeaf"),
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        65_536,
+        Some("bc0c5527db17c68941a243c65b0aacee"),
+        Some("dd6583039a1a6a7cc32e5a281690d46ef17ea9998d850254afd5dfbba96e2abf"),
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        1 << 32,
+        None,
+        None,
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        u64::MAX,
+        Some("4c4d339b7a7a51b86b5396edded6caf6"),
+        Some("0490b021c87723e17d6f136e205c03f144ff94f99355b6a300ee46b338ee2381"),
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        0,
+        Some("ed4aac7877846395d167e87b57c24cfa"),
+        Some("89ee9aac7d30d38f74a10c30aa28d168c72879ed996da3a5f4ae548df7d51abd"),
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        1,
+        None,
+        None,
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        2,
+        Some("7d4a1767d827e6a19965e2c75720bade"),
+        Some("f75af9573e969e1455cc99c34e0639f925c261a8d62ed7cbf6ff2779cd7b5498"),
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        3,
+        Some("384bdf8fa615ff9e9e1ac24411aa59aa"),
+        Some("c944af6df7c9597da73026858eca8453af951e9222cf78f7d53d581865e5cee4"),
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        255,
+        Some("df923ac69cc92fca2f4028beed28a4d0"),
+        Some("5474d6e1ba1a6c708eda5cd6593c815837e38cdb85a8e34cce936cb2501066a1"),
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        65_536,
+        Some("4e8b59a09a7607c58f29dc45e30478fe"),
+        Some("7ec2a04d4a3c5d0ab6e297333638df5fad7e3daa1649220255f491f9d78f3ded"),
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        1 << 32,
+        None,
+        None,
+    ),
+    (
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        u64::MAX,
+        Some("5108578cb99723d844be8edfc28024e3"),
+        Some("20be1cb5b695bdf05202c1daa924bd2a9a2234d65c64a2ab9ab2b9d1073baba5"),
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        0,
+        Some("167f9d8afb668890116022acf803d1fd"),
+        Some("cec5bb14ca5648b68eccc0b0bac428af5dbb1db3206a6422999366fd24bf1fdf"),
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        1,
+        None,
+        None,
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        2,
+        Some("84adcbae90b879c942a314d6e8adf7e3"),
+        Some("f810b410dd356add8712bb534fbddb61a9455561e6dfca5dcbc588fbea2fedc2"),
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        3,
+        None,
+        None,
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        255,
+        Some("f57a79c0375a73c37e93b4a4e9816ccf"),
+        Some("6dc37ddbb3afd41527429de5e7c903ea4efe56f6ec8ea413b51bbe47ea1df067"),
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        65_536,
+        Some("976a626b4e06929f810c3862e885c7fb"),
+        Some("8b1e12590421859c4d06761bf54da9d1b2446e78adbfb2d589afbf4f49641528"),
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        1 << 32,
+        Some("8a0cab0dff62fb9b2c314f3194a4f1ee"),
+        Some("ae35f75d1d81cb5d67f8000d072f5ce6c55d26b943026350062fcea15c8222b3"),
+    ),
+    (
+        "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a",
+        u64::MAX,
+        Some("123423996c251ead3804c594d90e82e6"),
+        Some("7ba929d7e117ea00d159581e5bd6054f580ee19949de331d1d4aad4b0166c142"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        0,
+        Some("583c118f4911af9e2e07b5be682bbbfd"),
+        Some("c4486c02a210988f98e217a992945538fe99fa09a4f9f6596006575ebd070f31"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        1,
+        Some("b911b513943b1e40783850d8e5cfa9d8"),
+        Some("0e373d1f90b521c9e0660cebb2fc75fddca55b03ffa3f23346765340088b95cd"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        2,
+        Some("c104322fb85546aab679f58741296bee"),
+        Some("4cb6ae1c478199210b84573bc4f99e816aaadb50ef8d804231f4f48497b46420"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        3,
+        Some("cc750097eaa414a7062fe735c23386dc"),
+        Some("a9f88126aa338723ebd7f96c2c121045296867fa0db18daee45e362f6034a691"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        255,
+        Some("2110aaa0b7a598b80b23d068eeb9b9bc"),
+        Some("79a6f70c95dc4b710a26562e3bc6bb73068a609731cd84efe9beeac36d86b493"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        65_536,
+        Some("a82d993ed94a636dbd24cf3c4a2af4f2"),
+        Some("d0a16defdcb5b371e23d1bd17d27405f1909fe1eaaa1925c637983aa7c236dec"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        1 << 32,
+        Some("a6132a43954044aa15832cab4a5325ed"),
+        Some("b46e4fe7c162bf491c9491972435251fc76db458750bfb2aa12d1fcbf07f56ed"),
+    ),
+    (
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        u64::MAX,
+        Some("4c08605638770d84a338b55344354ab0"),
+        Some("8100b2841bbc6987dfeb187753cdc2fb24796aa1796cdfa49cdff99fd960c084"),
+    ),
+];
+
+/// A challenge and nonce with the digest a correct drillx build produces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestVector {
+    pub challenge: String,
+    pub nonce: u64,
+    pub digest: Option<String>,
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VectorMismatch {
+    pub challenge: String,
+    pub nonce: u64,
+    pub expected_digest: Option<String>,
+    pub actual_digest: Option<String>,
+    pub expected_hash: Option<String>,
+    pub actual_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyOutput {
+    pub source: String,
+    pub checked: usize,
+    pub invalid_solutions: usize,
+    pub mismatches: Vec<VectorMismatch>,
+    pub passed: bool,
+}
+
+impl CommandOutput for VerifyOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![format!(
+            "Checked {} vectors from {}",
+            self.checked, self.source
+        )];
+        if self.invalid_solutions > 0 {
+            lines.push(format!(
+                "{} {} solutions failed on-chain validation",
+                "ERROR".bold().red(),
+                self.invalid_solutions
+            ));
+        }
+        for mismatch in self.mismatches.iter() {
+            lines.push(format!(
+                "{} challenge {} nonce {}: expected {} got {}",
+                "MISMATCH".bold().red(),
+                mismatch.challenge,
+                mismatch.nonce,
+                mismatch.expected_hash.as_deref().unwrap_or("no solution"),
+                mismatch.actual_hash.as_deref().unwrap_or("no solution")
+            ));
+        }
+        if self.passed {
+            lines.push(format!("{} drillx output matches", "OK".bold().green()));
+        } else {
+            lines.push(format!(
+                "{} drillx output does not match. Do not mine with this build.",
+                "FAILED".bold().red()
+            ));
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let rows = self
+            .mismatches
+            .iter()
+            .map(|mismatch| {
+                vec![
+                    mismatch.challenge.clone(),
+                    mismatch.nonce.to_string(),
+                    mismatch.expected_hash.clone().unwrap_or_default(),
+                    mismatch.actual_hash.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        (vec!["Challenge", "Nonce", "Expected", "Actual"], rows)
+    }
+}
+
+impl Miner {
+    /// Hashes the fixed test vectors and compares them against known-good digests.
+    pub async fn benchmark_verify(&self, args: BenchmarkArgs) {
+        // Record vectors from this build
+        if let Some(path) = args.record_vectors {
+            let vectors = compute_vectors();
+            match serde_json::to_string_pretty(&vectors) {
+                Ok(content) => match fs::write(&path, content) {
+                    Ok(()) => println!("Recorded {} vectors to {}", vectors.len(), path),
+                    Err(err) => {
+                        println!("{} Failed to write {}: {}", "ERROR".bold().red(), path, err)
+                    }
+                },
+                Err(err) => println!(
+                    "{} Failed to serialize vectors: {}",
+                    "ERROR".bold().red(),
+                    err
+                ),
+            }
+            return;
+        }
+
+        // Load expected vectors
+        let (source, expected) = match args.vectors {
+            Some(path) => {
+                let vectors = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|content| {
+                        serde_json::from_str::<Vec<TestVector>>(&content)
+                            .map_err(|err| err.to_string())
+                    });
+                match vectors {
+                    Ok(vectors) => (path, vectors),
+                    Err(err) => {
+                        println!("{} Failed to load {}: {}", "ERROR".bold().red(), path, err);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            None => ("embedded vectors".to_string(), embedded_vectors()),
+        };
+
+        // Validity checks run on the same drillx build, so they cannot catch a bad one alone
+        if expected.is_empty() {
+            println!(
+                "{} No expected digests in {}. Record them with --record-vectors on a known-good build and pass them with --vectors.",
+                "ERROR".bold().red(),
+                source
+            );
+            std::process::exit(1);
+        }
+
+        // Hash and compare
+        let actual = compute_vectors();
+        let invalid_solutions = actual
+            .iter()
+            .filter(|vector| !is_valid_solution(vector))
+            .count();
+        let mut mismatches = vec![];
+        for expected in expected.iter() {
+            let actual = actual
+                .iter()
+                .find(|v| v.challenge.eq(&expected.challenge) && v.nonce.eq(&expected.nonce))
+                .cloned()
+                .unwrap_or_else(|| hash_vector(&expected.challenge, expected.nonce));
+            if actual.digest.ne(&expected.digest) || actual.hash.ne(&expected.hash) {
+                mismatches.push(VectorMismatch {
+                    challenge: expected.challenge.clone(),
+                    nonce: expected.nonce,
+                    expected_digest: expected.digest.clone(),
+                    actual_digest: actual.digest,
+                    expected_hash: expected.hash.clone(),
+                    actual_hash: actual.hash,
+                });
+            }
+        }
+        let passed = invalid_solutions == 0 && mismatches.is_empty();
+        let output = VerifyOutput {
+            source,
+            checked: expected.len(),
+            invalid_solutions,
+            mismatches,
+            passed,
+        };
+        self.print_output(&output);
+        if !passed {
+            std::process::exit(1);
+        }
+    }
+}
+
+fn embedded_vectors() -> Vec<TestVector> {
+    EXPECTED_VECTORS
+        .iter()
+        .map(|(challenge, nonce, digest, hash)| TestVector {
+            challenge: challenge.to_string(),
+            nonce: *nonce,
+            digest: digest.map(str::to_string),
+            hash: hash.map(str::to_string),
+        })
+        .collect()
+}
+
+/// Hashes every fixed challenge and nonce, reusing solver memory as mining does.
+fn compute_vectors() -> Vec<TestVector> {
+    let mut memory = equix::SolverMemory::new();
+    let mut vectors = vec![];
+    for challenge in VECTOR_CHALLENGES.iter() {
+        for nonce in VECTOR_NONCES.iter() {
+            let hx = drillx::hash_with_memory(&mut memory, challenge, &nonce.to_le_bytes()).ok();
+            vectors.push(TestVector {
+                challenge: hex::encode(challenge),
+                nonce: *nonce,
+                digest: hx.as_ref().map(|hx| hex::encode(hx.d)),
+                hash: hx.as_ref().map(|hx| hex::encode(hx.h)),
+            });
+        }
+    }
+    vectors
+}
+
+/// Hashes a single vector that is not part of the fixed set.
+fn hash_vector(challenge: &str, nonce: u64) -> TestVector {
+    let hx = hex::decode(challenge)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|challenge| drillx::hash(&challenge, &nonce.to_le_bytes()).ok());
+    TestVector {
+        challenge: challenge.to_string(),
+        nonce,
+        digest: hx.as_ref().map(|hx| hex::encode(hx.d)),
+        hash: hx.as_ref().map(|hx| hex::encode(hx.h)),
+    }
+}
+
+/// Checks a computed digest the same way the program validates a submitted solution.
+fn is_valid_solution(vector: &TestVector) -> bool {
+    let Some(digest) = &vector.digest else {
+        return true;
+    };
+    let challenge = hex::decode(&vector.challenge)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+    let digest = hex::decode(digest)
+        .ok()
+        .and_then(|bytes| <[u8; 16]>::try_from(bytes).ok());
+    match (challenge, digest) {
+        (Some(challenge), Some(digest)) => {
+            Solution::new(digest, vector.nonce.to_le_bytes()).is_valid(&challenge)
+        }
+        _ => false,
+    }
+}
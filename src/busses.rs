@@ -1,23 +1,236 @@
+use std::{collections::VecDeque, time::Duration};
+
 use ore_api::{
-    consts::{BUS_ADDRESSES, TOKEN_DECIMALS},
+    consts::{BUS_ADDRESSES, BUS_COUNT, EPOCH_DURATION, TOKEN_DECIMALS, TREASURY_TOKENS_ADDRESS},
     state::Bus,
 };
 use ore_utils::AccountDeserialize;
+use serde::Serialize;
+use spl_token::amount_to_ui_amount;
+
+use crate::{
+    args::BussesArgs,
+    output::{CommandOutput, OutputFormat},
+    utils::get_config,
+    Miner,
+};
+
+/// Minimum time watch mode looks back when measuring how fast a bus is being drained.
+/// The window is `max(60s, 2 × interval)`, so it always spans at least one earlier sample.
+const DEPLETION_WINDOW_SECS: i64 = 60;
 
-use crate::Miner;
+#[derive(Debug, Serialize)]
+pub struct BusBalance {
+    pub id: u64,
+    pub address: String,
+    pub rewards: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depletion_per_minute: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minutes_to_exhaustion: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exhausted_before_reset: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BussesOutput {
+    pub busses: Vec<BusBalance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub treasury_balance: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds_to_reset: Option<i64>,
+}
+
+impl CommandOutput for BussesOutput {
+    fn to_text(&self) -> String {
+        let mut lines: Vec<String> = self
+            .busses
+            .iter()
+            .map(|b| {
+                let mut line = format!("Bus {}: {:} ORE", b.id, b.rewards);
+                if let Some(rate) = b.depletion_per_minute {
+                    line.push_str(&format!(" ({:.4} ORE/min", rate));
+                    if let Some(minutes) = b.minutes_to_exhaustion {
+                        line.push_str(&format!(", empty in {:.1} min", minutes));
+                        if b.exhausted_before_reset == Some(true) {
+                            line.push_str(" before reset");
+                        }
+                    }
+                    line.push(')');
+                }
+                line
+            })
+            .collect();
+        if let Some(balance) = self.treasury_balance {
+            lines.push(format!("Treasury: {} ORE", balance));
+        }
+        if let Some(seconds) = self.seconds_to_reset {
+            lines.push(format!("Next reset in {} sec", seconds));
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let fmt_option = |value: Option<f64>, precision: usize| {
+            value.map_or("-".to_string(), |v| format!("{:.*}", precision, v))
+        };
+        let mut rows: Vec<Vec<String>> = self
+            .busses
+            .iter()
+            .map(|b| {
+                vec![
+                    b.id.to_string(),
+                    b.address.clone(),
+                    b.rewards.to_string(),
+                    fmt_option(b.depletion_per_minute, 4),
+                    fmt_option(b.minutes_to_exhaustion, 1),
+                    b.exhausted_before_reset.map_or("-".to_string(), |before| {
+                        if before { "yes" } else { "no" }.into()
+                    }),
+                ]
+            })
+            .collect();
+        if let Some(balance) = self.treasury_balance {
+            rows.push(vec![
+                "Treasury".into(),
+                TREASURY_TOKENS_ADDRESS.to_string(),
+                balance.to_string(),
+                "-".into(),
+                "-".into(),
+                "-".into(),
+            ]);
+        }
+        (
+            vec![
+                "Bus",
+                "Address",
+                "Rewards (ORE)",
+                "Depletion (ORE/min)",
+                "Empty in (min)",
+                "Empty before reset",
+            ],
+            rows,
+        )
+    }
+}
 
 impl Miner {
-    pub async fn busses(&self) {
-        let client = self.rpc_client.clone();
-        for address in BUS_ADDRESSES.iter() {
-            let data = client.get_account_data(address).await.unwrap();
-            match Bus::try_from_bytes(&data) {
-                Ok(bus) => {
-                    let rewards = (bus.rewards as f64) / 10f64.powf(TOKEN_DECIMALS as f64);
-                    println!("Bus {}: {:} ORE", bus.id, rewards);
+    pub async fn busses(&self, args: BussesArgs) {
+        if args.watch {
+            self.watch_busses(args.interval).await;
+            return;
+        }
+        match self.fetch_busses().await {
+            Ok(busses) => self.print_output(&BussesOutput {
+                busses: busses
+                    .iter()
+                    .map(|bus| BusBalance {
+                        id: bus.id,
+                        address: BUS_ADDRESSES[bus.id as usize].to_string(),
+                        rewards: amount_to_ui_amount(bus.rewards, TOKEN_DECIMALS),
+                        depletion_per_minute: None,
+                        minutes_to_exhaustion: None,
+                        exhausted_before_reset: None,
+                    })
+                    .collect(),
+                treasury_balance: None,
+                seconds_to_reset: None,
+            }),
+            Err(err) => println!("Failed to fetch bus accounts: {}", err),
+        }
+    }
+
+    /// Polls the bus and treasury accounts and redraws their balances until interrupted.
+    async fn watch_busses(&self, interval: u64) {
+        let mut samples: Vec<VecDeque<(i64, u64)>> = vec![VecDeque::new(); BUS_COUNT];
+        let window = DEPLETION_WINDOW_SECS.max((interval as i64).saturating_mul(2));
+        loop {
+            let busses = match self.fetch_busses().await {
+                Ok(busses) => busses,
+                Err(err) => {
+                    tracing::warn!("Failed to fetch bus accounts: {}", err);
+                    tokio::time::sleep(Duration::from_secs(interval)).await;
+                    continue;
+                }
+            };
+            let now = chrono::Utc::now().timestamp();
+            let config = get_config(&self.rpc_client).await;
+            let seconds_to_reset = (config.last_reset_at + EPOCH_DURATION - now).max(0);
+            let treasury_balance = match self
+                .rpc_client
+                .get_token_account_balance(&TREASURY_TOKENS_ADDRESS)
+                .await
+            {
+                Ok(balance) => balance.ui_amount,
+                Err(err) => {
+                    tracing::warn!("Failed to fetch treasury balance: {}", err);
+                    None
+                }
+            };
+
+            // Measure how fast each bus is draining over the recent window
+            let mut balances = vec![];
+            for bus in busses.iter() {
+                let history = &mut samples[bus.id as usize];
+
+                // Rewards only go up when the epoch resets, which restarts the measurement
+                if history
+                    .back()
+                    .is_some_and(|(_, rewards)| bus.rewards > *rewards)
+                {
+                    history.clear();
                 }
-                Err(_) => {}
+                history.push_back((now, bus.rewards));
+                while history.front().is_some_and(|(at, _)| now - at > window) {
+                    history.pop_front();
+                }
+                let depletion_per_minute = match (history.front(), history.back()) {
+                    (Some((start_at, start)), Some((end_at, end))) if end_at > start_at => {
+                        let drained = amount_to_ui_amount(start - end, TOKEN_DECIMALS);
+                        Some(drained * 60.0 / (end_at - start_at) as f64)
+                    }
+                    _ => None,
+                };
+                let rewards = amount_to_ui_amount(bus.rewards, TOKEN_DECIMALS);
+                let minutes_to_exhaustion = depletion_per_minute
+                    .filter(|rate| *rate > 0.0)
+                    .map(|rate| rewards / rate);
+                let exhausted_before_reset =
+                    minutes_to_exhaustion.map(|minutes| minutes * 60.0 < seconds_to_reset as f64);
+                balances.push(BusBalance {
+                    id: bus.id,
+                    address: BUS_ADDRESSES[bus.id as usize].to_string(),
+                    rewards,
+                    depletion_per_minute,
+                    minutes_to_exhaustion,
+                    exhausted_before_reset,
+                });
             }
+
+            // Redraw in place unless the output is meant for another program
+            if self.output != OutputFormat::Json {
+                print!("\x1B[2J\x1B[1;1H");
+            }
+            self.print_output(&BussesOutput {
+                busses: balances,
+                treasury_balance,
+                seconds_to_reset: Some(seconds_to_reset),
+            });
+            tokio::time::sleep(Duration::from_secs(interval)).await;
         }
     }
+
+    /// Fetches every bus account in a single request.
+    async fn fetch_busses(&self) -> Result<Vec<Bus>, Box<dyn std::error::Error>> {
+        let accounts = self
+            .rpc_client
+            .get_multiple_accounts(&BUS_ADDRESSES)
+            .await?;
+        Ok(accounts
+            .into_iter()
+            .flatten()
+            .filter_map(|account| Bus::try_from_bytes(&account.data).ok().copied())
+            .filter(|bus| (bus.id as usize) < BUS_COUNT)
+            .collect())
+    }
 }
@@ -2,23 +2,41 @@ use std::str::FromStr;
 
 use colored::*;
 use ore_api::consts::MINT_ADDRESS;
-use solana_program::pubkey::Pubkey;
-use solana_sdk::signature::Signer;
+use solana_client::client_error::Result as ClientResult;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_sdk::signature::Signature;
 use spl_token::amount_to_ui_amount;
 
 use crate::{
     args::ClaimArgs,
-    cu_limits::CU_LIMIT_CLAIM,
+    cu_limits::{CU_LIMIT_ATA, CU_LIMIT_CLAIM},
     send_and_confirm::ComputeBudget,
+    signer::MinerSigner,
     utils::{amount_f64_to_u64, ask_confirm, get_proof_with_authority},
     Miner,
 };
 
 impl Miner {
     pub async fn claim(&self, args: ClaimArgs) {
-        let signer = self.signer();
-        let pubkey = signer.pubkey();
-        let proof = get_proof_with_authority(&self.rpc_client, pubkey).await;
+        self.claim_from(&self.signer(), args, true).await;
+    }
+
+    /// Claims rewards from the authority's proof, returning the amount claimed, the
+    /// beneficiary token account and the result of sending the transaction, or `None` if
+    /// nothing was sent. Only prompts for confirmation when `confirm` is set.
+    pub async fn claim_from(
+        &self,
+        authority: &MinerSigner,
+        args: ClaimArgs,
+        confirm: bool,
+    ) -> Option<(u64, Pubkey, ClientResult<Option<Signature>>)> {
+        // Signing offline rules out looking up the claimable balance
+        if self.offline() && args.amount.is_none() {
+            println!("Signing with --blockhash requires --amount");
+            return None;
+        }
+
+        let pubkey = authority.pubkey();
         let mut ixs = vec![];
         let beneficiary = match args.to {
             Some(to) => {
@@ -28,15 +46,15 @@ impl Miner {
                     &wallet,
                     &MINT_ADDRESS,
                 );
-                if self
-                    .rpc_client
-                    .get_token_account(&benefiary_tokens)
-                    .await
-                    .is_err()
+                if self.offline()
+                    || !matches!(
+                        self.rpc_client.get_token_account(&benefiary_tokens).await,
+                        Ok(Some(_))
+                    )
                 {
                     ixs.push(
-                        spl_associated_token_account::instruction::create_associated_token_account(
-                            &signer.pubkey(),
+                        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                            &pubkey,
                             &wallet,
                             &ore_api::consts::MINT_ADDRESS,
                             &spl_token::id(),
@@ -45,63 +63,77 @@ impl Miner {
                 }
                 benefiary_tokens
             }
-            None => self.initialize_ata().await,
+            None => self.initialize_ata(pubkey, &mut ixs).await,
         };
 
         // Parse amount to claim
         let amount = if let Some(amount) = args.amount {
             amount_f64_to_u64(amount)
         } else {
-            proof.balance
+            get_proof_with_authority(&self.rpc_client, pubkey)
+                .await
+                .balance
         };
 
         // Confirm user wants to claim
-        if !ask_confirm(
-            format!(
-                "\nYou are about to claim {}.\n\nAre you sure you want to continue? [Y/n]",
+        if confirm
+            && !self.dry_run
+            && !ask_confirm(
                 format!(
-                    "{} ORE",
-                    amount_to_ui_amount(amount, ore_api::consts::TOKEN_DECIMALS)
+                    "\nYou are about to claim {}.\n\nAre you sure you want to continue?",
+                    format!(
+                        "{} ORE",
+                        amount_to_ui_amount(amount, ore_api::consts::TOKEN_DECIMALS)
+                    )
+                    .bold(),
                 )
-                .bold(),
+                .as_str(),
+                self.assume_yes,
             )
-            .as_str(),
-        ) {
-            return;
+        {
+            return None;
         }
 
         // Send and confirm
+        let cu_limit = CU_LIMIT_CLAIM + CU_LIMIT_ATA * ixs.len() as u32;
         ixs.push(ore_api::instruction::claim(pubkey, beneficiary, amount));
-        self.send_and_confirm(&ixs, ComputeBudget::Fixed(CU_LIMIT_CLAIM), false)
-            .await
-            .ok();
+        let result = self
+            .send_and_confirm_with_signers(
+                &ixs,
+                &[authority.clone()],
+                ComputeBudget::Fixed(cu_limit),
+                false,
+            )
+            .await;
+        Some((amount, beneficiary, result))
     }
 
-    async fn initialize_ata(&self) -> Pubkey {
+    async fn initialize_ata(&self, wallet: Pubkey, ixs: &mut Vec<Instruction>) -> Pubkey {
         // Initialize client.
-        let signer = self.signer();
         let client = self.rpc_client.clone();
 
         // Build instructions.
         let token_account_pubkey = spl_associated_token_account::get_associated_token_address(
-            &signer.pubkey(),
+            &wallet,
             &ore_api::consts::MINT_ADDRESS,
         );
 
         // Check if ata already exists
-        if let Ok(Some(_ata)) = client.get_token_account(&token_account_pubkey).await {
-            return token_account_pubkey;
+        if !self.offline() {
+            if let Ok(Some(_ata)) = client.get_token_account(&token_account_pubkey).await {
+                return token_account_pubkey;
+            }
         }
-        // Sign and send transaction.
-        let ix = spl_associated_token_account::instruction::create_associated_token_account(
-            &signer.pubkey(),
-            &signer.pubkey(),
-            &ore_api::consts::MINT_ADDRESS,
-            &spl_token::id(),
+
+        // Create the ata in the same transaction
+        ixs.push(
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &wallet,
+                &wallet,
+                &ore_api::consts::MINT_ADDRESS,
+                &spl_token::id(),
+            ),
         );
-        self.send_and_confirm(&[ix], ComputeBudget::Dynamic, false)
-            .await
-            .ok();
 
         // Return token account address
         token_account_pubkey
@@ -1,5 +1,4 @@
 use colored::*;
-use solana_sdk::signature::Signer;
 use spl_token::amount_to_ui_amount;
 
 use crate::{
@@ -16,12 +15,13 @@ impl Miner {
         let proof = get_proof_with_authority(&self.rpc_client, signer.pubkey()).await;
 
         // Confirm the user wants to close.
-        if !ask_confirm(
-            format!("{} You have {} ORE staked in this account.\nAre you sure you want to {}close this account? [Y/n]", 
+        if !self.dry_run && !ask_confirm(
+            format!("{} You have {} ORE staked in this account.\nAre you sure you want to {}close this account?", 
                 "WARNING".yellow(),
                 amount_to_ui_amount(proof.balance, ore_api::consts::TOKEN_DECIMALS),
                 if proof.balance.gt(&0) { "claim your stake and "} else { "" }
-            ).as_str()
+            ).as_str(),
+            self.assume_yes,
         ) {
             return;
         }
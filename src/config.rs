@@ -1,20 +1,50 @@
 use colored::Colorize;
+use ore_api::consts::TOKEN_DECIMALS;
+use serde::Serialize;
+use spl_token::amount_to_ui_amount;
 
-use crate::{
-    utils::{amount_u64_to_string, get_config},
-    Miner,
-};
+use crate::{output::CommandOutput, utils::get_config, Miner};
+
+#[derive(Debug, Serialize)]
+pub struct ConfigOutput {
+    pub last_reset_at: i64,
+    pub min_difficulty: u64,
+    pub base_reward_rate: u64,
+    pub top_balance: f64,
+}
+
+impl CommandOutput for ConfigOutput {
+    fn to_text(&self) -> String {
+        [
+            format!("{}: {}", "Last reset at".bold(), self.last_reset_at),
+            format!("{}: {}", "Min difficulty".bold(), self.min_difficulty),
+            format!("{}: {}", "Base reward rate".bold(), self.base_reward_rate),
+            format!("{}: {} ORE", "Top stake".bold(), self.top_balance),
+        ]
+        .join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        (
+            vec!["Field", "Value"],
+            vec![
+                vec!["Last reset at".into(), self.last_reset_at.to_string()],
+                vec!["Min difficulty".into(), self.min_difficulty.to_string()],
+                vec!["Base reward rate".into(), self.base_reward_rate.to_string()],
+                vec!["Top stake (ORE)".into(), self.top_balance.to_string()],
+            ],
+        )
+    }
+}
 
 impl Miner {
     pub async fn config(&self) {
         let config = get_config(&self.rpc_client).await;
-        println!("{}: {}", "Last reset at".bold(), config.last_reset_at);
-        println!("{}: {}", "Min difficulty".bold(), config.min_difficulty);
-        println!("{}: {}", "Base reward rate".bold(), config.base_reward_rate);
-        println!(
-            "{}: {} ORE",
-            "Top stake".bold(),
-            amount_u64_to_string(config.top_balance)
-        );
+        self.print_output(&ConfigOutput {
+            last_reset_at: config.last_reset_at,
+            min_difficulty: config.min_difficulty as u64,
+            base_reward_rate: config.base_reward_rate,
+            top_balance: amount_to_ui_amount(config.top_balance, TOKEN_DECIMALS),
+        });
     }
 }
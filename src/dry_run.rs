@@ -0,0 +1,170 @@
+use colored::*;
+use ore_api::consts::{MINT_ADDRESS, MINT_V1_ADDRESS, TOKEN_DECIMALS, TOKEN_DECIMALS_V1};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+};
+use solana_program::{native_token::lamports_to_sol, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::{account::Account, transaction::Transaction};
+use solana_transaction_status::UiTransactionEncoding;
+use spl_token::amount_to_ui_amount;
+
+use crate::{send_and_confirm::BASE_FEE, Miner};
+
+impl Miner {
+    /// Simulates a fully built transaction and reports what sending it would do.
+    pub async fn dry_run(
+        &self,
+        tx: &Transaction,
+        cu_limit: u32,
+        cu_price: u64,
+    ) -> ClientResult<()> {
+        let client = self.rpc_client.clone();
+        let message = &tx.message;
+
+        // Print instructions and accounts
+        println!("{}", "DRY RUN".bold().yellow());
+        println!("{}: {}", "Fee payer".bold(), message.account_keys[0]);
+        for (i, ix) in message.instructions.iter().enumerate() {
+            println!(
+                "{} {}: {}",
+                "Instruction".bold(),
+                i,
+                message.account_keys[ix.program_id_index as usize]
+            );
+        }
+        println!("{}", "Accounts".bold());
+        for (i, key) in message.account_keys.iter().enumerate() {
+            println!(
+                "  {} {}{}",
+                key,
+                if message.is_writable(i) { "w" } else { "r" },
+                if message.is_signer(i) { "s" } else { "" }
+            );
+        }
+
+        // Snapshot writable accounts before simulation
+        let writable: Vec<Pubkey> = message
+            .account_keys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| message.is_writable(*i))
+            .map(|(_, key)| *key)
+            .collect();
+        let pre_accounts = client.get_multiple_accounts(&writable).await?;
+
+        // Simulate
+        let sim_res = client
+            .simulate_transaction_with_config(
+                tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(self.rpc_client.commitment()),
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    accounts: Some(RpcSimulateTransactionAccountsConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        addresses: writable.iter().map(|key| key.to_string()).collect(),
+                    }),
+                    min_context_slot: None,
+                    inner_instructions: false,
+                },
+            )
+            .await?
+            .value;
+
+        // Estimate fee
+        let base_fee = BASE_FEE * message.header.num_required_signatures as u64;
+        let priority_fee = (cu_limit as u64).saturating_mul(cu_price) / 1_000_000;
+        println!(
+            "{}: {} SOL (base {} + priority {} lamports)",
+            "Estimated fee".bold(),
+            lamports_to_sol(base_fee + priority_fee),
+            base_fee,
+            priority_fee
+        );
+        if let Some(units_consumed) = sim_res.units_consumed {
+            println!(
+                "{}: {} / {}",
+                "Compute units".bold(),
+                units_consumed,
+                cu_limit
+            );
+        }
+
+        // Print balance changes
+        if let Some(post_accounts) = sim_res.accounts {
+            println!("{}", "Balance changes".bold());
+            for ((key, pre), post) in writable.iter().zip(pre_accounts).zip(post_accounts) {
+                let post: Option<Account> = post.and_then(|ui| ui.decode());
+                print_balance_change(key, pre.as_ref(), post.as_ref());
+            }
+        }
+
+        // Print logs
+        if let Some(logs) = sim_res.logs {
+            println!("{}", "Logs".bold());
+            for log in logs {
+                println!("  {}", log);
+            }
+        }
+
+        // Return simulation error
+        if let Some(err) = sim_res.err {
+            println!("{}: {}", "ERROR".bold().red(), err);
+            return Err(ClientError {
+                request: None,
+                kind: ClientErrorKind::Custom(err.to_string()),
+            });
+        }
+
+        println!(
+            "{} Simulation succeeded. Transaction not sent.",
+            "OK".bold().green()
+        );
+        Ok(())
+    }
+}
+
+fn print_balance_change(key: &Pubkey, pre: Option<&Account>, post: Option<&Account>) {
+    // Lamport changes
+    let pre_lamports = pre.map(|a| a.lamports).unwrap_or(0) as i128;
+    let post_lamports = post.map(|a| a.lamports).unwrap_or(0) as i128;
+    if pre_lamports != post_lamports {
+        println!(
+            "  {}: {:+} SOL",
+            key,
+            (post_lamports - pre_lamports) as f64 / 1e9
+        );
+    }
+
+    // Token amount changes
+    let pre_tokens = pre.and_then(token_amount);
+    let post_tokens = post.and_then(token_amount);
+    if let Some((mint, _)) = pre_tokens.or(post_tokens) {
+        let pre_amount = pre_tokens.map(|(_, amount)| amount).unwrap_or(0);
+        let post_amount = post_tokens.map(|(_, amount)| amount).unwrap_or(0);
+        if pre_amount != post_amount {
+            let decimals = if mint == MINT_ADDRESS {
+                TOKEN_DECIMALS
+            } else if mint == MINT_V1_ADDRESS {
+                TOKEN_DECIMALS_V1
+            } else {
+                0
+            };
+            let pre_ui = amount_to_ui_amount(pre_amount, decimals);
+            let post_ui = amount_to_ui_amount(post_amount, decimals);
+            println!("  {}: {:+} tokens ({})", key, post_ui - pre_ui, mint);
+        }
+    }
+}
+
+fn token_amount(account: &Account) -> Option<(Pubkey, u64)> {
+    if account.owner != spl_token::id() {
+        return None;
+    }
+    spl_token::state::Account::unpack(&account.data)
+        .ok()
+        .map(|token_account| (token_account.mint, token_account.amount))
+}
@@ -0,0 +1,249 @@
+use std::str::FromStr;
+
+use ore_api::consts::TOKEN_DECIMALS;
+use serde::Serialize;
+use solana_program::{native_token::lamports_to_sol, pubkey::Pubkey};
+use spl_token::amount_to_ui_amount;
+
+use crate::{
+    args::EstimateArgs,
+    benchmark::BenchmarkCache,
+    cu_limits::CU_LIMIT_MINE,
+    output::CommandOutput,
+    progress::Progress,
+    send_and_confirm::BASE_FEE,
+    utils::{get_config, get_proof_with_authority, stake_multiplier},
+    Miner,
+};
+
+/// Proofs can submit at most once per minute.
+const ROUNDS_PER_HOUR: f64 = 60.0;
+
+#[derive(Debug, Serialize)]
+pub struct EstimateOutput {
+    pub cores: u64,
+    pub hashrate: u64,
+    pub hashes_per_round: u64,
+    pub expected_best_difficulty: f64,
+    pub submission_probability: f64,
+    pub stake_multiplier: f64,
+    pub ore_per_hour: f64,
+    pub ore_per_day: f64,
+    pub sol_fee_per_hour: f64,
+    pub sol_fee_per_day: f64,
+}
+
+impl CommandOutput for EstimateOutput {
+    fn to_text(&self) -> String {
+        [
+            format!("Hashrate: {} H/sec ({} cores)", self.hashrate, self.cores),
+            format!("Hashes per round: {}", self.hashes_per_round),
+            format!(
+                "Expected best difficulty: {:.2}",
+                self.expected_best_difficulty
+            ),
+            format!(
+                "Rounds reaching min difficulty: {:.2}%",
+                self.submission_probability * 100.0
+            ),
+            format!("Stake multiplier: {:.4}x", self.stake_multiplier),
+            format!("Estimated rewards: {:.6} ORE/hour", self.ore_per_hour),
+            format!("Estimated rewards: {:.6} ORE/day", self.ore_per_day),
+            format!("Estimated fees: {:.6} SOL/hour", self.sol_fee_per_hour),
+            format!("Estimated fees: {:.6} SOL/day", self.sol_fee_per_day),
+        ]
+        .join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        (
+            vec!["Period", "Rewards (ORE)", "Fees (SOL)"],
+            vec![
+                vec![
+                    "Hour".into(),
+                    format!("{:.6}", self.ore_per_hour),
+                    format!("{:.6}", self.sol_fee_per_hour),
+                ],
+                vec![
+                    "Day".into(),
+                    format!("{:.6}", self.ore_per_day),
+                    format!("{:.6}", self.sol_fee_per_day),
+                ],
+            ],
+        )
+    }
+}
+
+impl Miner {
+    pub async fn estimate(&self, args: EstimateArgs) {
+        // Reuse the cached benchmark if it was run with the same core count
+        let hashrate = match BenchmarkCache::load() {
+            Some(cache) if cache.cores == args.cores && !args.refresh => cache.hashrate,
+            _ => {
+                self.check_num_cores(args.cores);
+                let progress_bar = Progress::new();
+                progress_bar.set_message(format!(
+                    "Benchmarking. This will take {} sec...",
+                    args.duration
+                ));
+                let hashrate = Self::measure_hashrate(args.cores, args.duration as i64);
+                progress_bar.finish_with_message(format!("Hashpower: {} H/sec", hashrate));
+                BenchmarkCache {
+                    cores: args.cores,
+                    hashrate,
+                    timestamp: chrono::Utc::now().timestamp(),
+                }
+                .save();
+                hashrate
+            }
+        };
+
+        // Get the stake multiplier
+        let config = get_config(&self.rpc_client).await;
+        let authority = match &args.address {
+            Some(address) => match Pubkey::from_str(address) {
+                Ok(address) => address,
+                Err(_) => {
+                    println!("Invalid address: {:?}", address);
+                    return;
+                }
+            },
+            None => self.signer().pubkey(),
+        };
+        let proof = get_proof_with_authority(&self.rpc_client, authority).await;
+        let multiplier = stake_multiplier(proof.balance, config.top_balance);
+
+        // Model the difficulty submitted in each round
+        let hashes_per_round = hashrate.saturating_mul(60u64.saturating_sub(args.buffer_time));
+        let min_difficulty = config.min_difficulty as u32;
+        let (expected_best_difficulty, expected_reward, submission_probability) =
+            expected_round_outcome(hashes_per_round, min_difficulty, config.base_reward_rate);
+
+        // Scale by rounds and compare with fees
+        let ore_per_hour = amount_to_ui_amount(
+            (expected_reward * multiplier * ROUNDS_PER_HOUR) as u64,
+            TOKEN_DECIMALS,
+        );
+        let fee_per_tx = BASE_FEE
+            + (CU_LIMIT_MINE as u64).saturating_mul(self.priority_fee.unwrap_or(0)) / 1_000_000;
+        let sol_fee_per_hour =
+            lamports_to_sol(fee_per_tx) * ROUNDS_PER_HOUR * submission_probability;
+        self.print_output(&EstimateOutput {
+            cores: args.cores,
+            hashrate,
+            hashes_per_round,
+            expected_best_difficulty,
+            submission_probability,
+            stake_multiplier: multiplier,
+            ore_per_hour,
+            ore_per_day: ore_per_hour * 24.0,
+            sol_fee_per_hour,
+            sol_fee_per_day: sol_fee_per_hour * 24.0,
+        });
+    }
+}
+
+/// Returns the expected best difficulty, expected reward and the probability of
+/// reaching the min difficulty for a round of at most `hashes` hashes.
+///
+/// Each drillx hash has difficulty of at least `d` with probability `2^-d`, so the
+/// best of `n` hashes reaches `d` with probability `1 - (1 - 2^-d)^n`. Mining stops
+/// at the first hash reaching the min difficulty, so the submitted difficulty only
+/// exceeds it by the excess of that one hash: `P(d >= min + k | d >= min) = 2^-k`.
+fn expected_round_outcome(
+    hashes: u64,
+    min_difficulty: u32,
+    base_reward_rate: u64,
+) -> (f64, f64, f64) {
+    let p_at_least = |d: u32| -> f64 {
+        if d == 0 {
+            return 1.0;
+        }
+        let p = 2f64.powi(-(d as i32));
+        1.0 - (hashes as f64 * (-p).ln_1p()).exp()
+    };
+    let p_found = p_at_least(min_difficulty);
+    let p_best_at_least = |d: u32| -> f64 {
+        if d <= min_difficulty {
+            p_at_least(d)
+        } else {
+            p_found * 2f64.powi(-((d - min_difficulty) as i32))
+        }
+    };
+    let mut expected_difficulty = 0.0;
+    let mut expected_reward = 0.0;
+    for d in 1..=128u32 {
+        let p_ge = p_best_at_least(d);
+        expected_difficulty += p_ge;
+        if d >= min_difficulty {
+            let p_eq = p_ge - p_best_at_least(d + 1);
+            let reward = base_reward_rate as f64 * 2f64.powi((d - min_difficulty).min(31) as i32);
+            expected_reward += p_eq * reward;
+        }
+    }
+    (expected_difficulty, expected_reward, p_found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expected_round_outcome;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn expected_round_outcome_without_hashes() {
+        let (difficulty, reward, probability) = expected_round_outcome(0, 8, 1_000);
+        assert_close(difficulty, 0.0);
+        assert_close(reward, 0.0);
+        assert_close(probability, 0.0);
+    }
+
+    #[test]
+    fn expected_round_outcome_of_one_hash() {
+        // The best of one hash is geometric: P(d >= k) = 2^-k, so E[d] = 1
+        let (difficulty, _, probability) = expected_round_outcome(1, 1, 1_000);
+        assert_close(difficulty, 1.0);
+        assert_close(probability, 0.5);
+        let (_, _, probability) = expected_round_outcome(1, 3, 1_000);
+        assert_close(probability, 0.125);
+    }
+
+    #[test]
+    fn expected_round_outcome_without_min_difficulty() {
+        let (_, _, probability) = expected_round_outcome(1, 0, 1_000);
+        assert_close(probability, 1.0);
+    }
+
+    #[test]
+    fn expected_round_outcome_pays_at_least_base_rate_on_success() {
+        let (_, reward, probability) = expected_round_outcome(1 << 20, 16, 1_000);
+        assert!(reward >= 1_000.0 * probability);
+    }
+
+    #[test]
+    fn expected_round_outcome_stops_at_min_difficulty() {
+        // A round that always reaches the min difficulty submits one hash past it,
+        // whose excess difficulty is geometric with mean 1
+        let (difficulty, _, probability) = expected_round_outcome(1 << 40, 8, 1_000);
+        assert_close(probability, 1.0);
+        assert_close(difficulty, 9.0);
+    }
+
+    #[test]
+    fn expected_round_outcome_grows_with_hashes() {
+        let (difficulty_small, reward_small, probability_small) =
+            expected_round_outcome(1_000, 16, 1_000);
+        let (difficulty_large, reward_large, probability_large) =
+            expected_round_outcome(1_000_000, 16, 1_000);
+        assert!(difficulty_large > difficulty_small);
+        assert!(reward_large > reward_small);
+        assert!(probability_large > probability_small);
+    }
+}
@@ -0,0 +1,221 @@
+use std::str::FromStr;
+
+use colored::Colorize;
+use ore_api::{
+    consts::{TOKEN_DECIMALS, TOKEN_DECIMALS_V1},
+    state::{Bus, Config, Proof, Treasury},
+};
+use ore_utils::AccountDeserialize;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::{json, Value};
+use solana_program::{native_token::lamports_to_sol, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::{account::Account, hash::Hash};
+use spl_token::amount_to_ui_amount;
+
+use crate::{args::InspectArgs, output::CommandOutput, Miner};
+
+#[derive(Debug, Serialize)]
+pub struct InspectOutput {
+    pub address: String,
+    pub owner: String,
+    pub lamports: u64,
+    pub account_type: String,
+    /// Decoded fields in declaration order.
+    #[serde(serialize_with = "serialize_fields")]
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl CommandOutput for InspectOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("{}: {}", "Address".bold(), self.address),
+            format!("{}: {}", "Type".bold(), self.account_type),
+            format!("{}: {}", "Owner".bold(), self.owner),
+            format!(
+                "{}: {} SOL",
+                "Balance".bold(),
+                lamports_to_sol(self.lamports)
+            ),
+        ];
+        for (name, value) in self.fields.iter() {
+            lines.push(format!("{}: {}", name.bold(), display_value(value)));
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let mut rows = vec![
+            vec!["Address".into(), self.address.clone()],
+            vec!["Type".into(), self.account_type.clone()],
+            vec!["Owner".into(), self.owner.clone()],
+            vec!["Lamports".into(), self.lamports.to_string()],
+        ];
+        for (name, value) in self.fields.iter() {
+            rows.push(vec![name.to_string(), display_value(value)]);
+        }
+        (vec!["Field", "Value"], rows)
+    }
+}
+
+impl Miner {
+    pub async fn inspect(&self, args: InspectArgs) {
+        let Ok(address) = Pubkey::from_str(&args.address) else {
+            println!("Invalid address: {:?}", args.address);
+            return;
+        };
+        let account = match self.rpc_client.get_account(&address).await {
+            Ok(account) => account,
+            Err(err) => {
+                println!("Failed to fetch account {}: {}", address, err);
+                return;
+            }
+        };
+        let (account_type, fields) = decode_account(&account);
+        self.print_output(&InspectOutput {
+            address: address.to_string(),
+            owner: account.owner.to_string(),
+            lamports: account.lamports,
+            account_type: account_type.to_string(),
+            fields,
+        });
+    }
+}
+
+/// Identifies an account by its owner and discriminator and decodes its fields.
+fn decode_account(account: &Account) -> (&'static str, Vec<(&'static str, Value)>) {
+    let data = account.data.as_slice();
+    if account.owner == ore_api::ID {
+        if let Ok(proof) = Proof::try_from_bytes(data) {
+            return (
+                "Proof",
+                vec![
+                    ("authority", json!(proof.authority.to_string())),
+                    (
+                        "balance",
+                        json!(amount_to_ui_amount(proof.balance, TOKEN_DECIMALS)),
+                    ),
+                    (
+                        "challenge",
+                        json!(Hash::new_from_array(proof.challenge).to_string()),
+                    ),
+                    (
+                        "last_hash",
+                        json!(Hash::new_from_array(proof.last_hash).to_string()),
+                    ),
+                    ("last_hash_at", json!(proof.last_hash_at)),
+                    ("last_stake_at", json!(proof.last_stake_at)),
+                    ("miner", json!(proof.miner.to_string())),
+                    ("total_hashes", json!(proof.total_hashes)),
+                    (
+                        "total_rewards",
+                        json!(amount_to_ui_amount(proof.total_rewards, TOKEN_DECIMALS)),
+                    ),
+                ],
+            );
+        }
+        if let Ok(bus) = Bus::try_from_bytes(data) {
+            return (
+                "Bus",
+                vec![
+                    ("id", json!(bus.id)),
+                    (
+                        "rewards",
+                        json!(amount_to_ui_amount(bus.rewards, TOKEN_DECIMALS)),
+                    ),
+                    (
+                        "theoretical_rewards",
+                        json!(amount_to_ui_amount(bus.theoretical_rewards, TOKEN_DECIMALS)),
+                    ),
+                    (
+                        "top_balance",
+                        json!(amount_to_ui_amount(bus.top_balance, TOKEN_DECIMALS)),
+                    ),
+                ],
+            );
+        }
+        if let Ok(config) = Config::try_from_bytes(data) {
+            return (
+                "Config",
+                vec![
+                    ("base_reward_rate", json!(config.base_reward_rate)),
+                    ("last_reset_at", json!(config.last_reset_at)),
+                    ("min_difficulty", json!(config.min_difficulty)),
+                    (
+                        "top_balance",
+                        json!(amount_to_ui_amount(config.top_balance, TOKEN_DECIMALS)),
+                    ),
+                ],
+            );
+        }
+        if Treasury::try_from_bytes(data).is_ok() {
+            return ("Treasury", vec![]);
+        }
+        return ("Unknown ORE account", vec![("data_len", json!(data.len()))]);
+    }
+
+    if account.owner == spl_token::id() {
+        if let Ok(token_account) = spl_token::state::Account::unpack(data) {
+            return (
+                "Token account",
+                vec![
+                    ("mint", json!(token_account.mint.to_string())),
+                    ("owner", json!(token_account.owner.to_string())),
+                    (
+                        "amount",
+                        json!(amount_to_ui_amount(
+                            token_account.amount,
+                            token_decimals(&token_account.mint)
+                        )),
+                    ),
+                    ("raw_amount", json!(token_account.amount)),
+                    ("state", json!(format!("{:?}", token_account.state))),
+                ],
+            );
+        }
+        if let Ok(mint) = spl_token::state::Mint::unpack(data) {
+            return (
+                "Token mint",
+                vec![
+                    ("decimals", json!(mint.decimals)),
+                    (
+                        "supply",
+                        json!(amount_to_ui_amount(mint.supply, mint.decimals)),
+                    ),
+                    (
+                        "mint_authority",
+                        json!(Option::<Pubkey>::from(mint.mint_authority).map(|a| a.to_string())),
+                    ),
+                ],
+            );
+        }
+    }
+
+    ("Unknown", vec![("data_len", json!(data.len()))])
+}
+
+fn token_decimals(mint: &Pubkey) -> u8 {
+    if *mint == ore_api::consts::MINT_V1_ADDRESS {
+        TOKEN_DECIMALS_V1
+    } else {
+        TOKEN_DECIMALS
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "None".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn serialize_fields<S: Serializer>(
+    fields: &[(&'static str, Value)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(fields.len()))?;
+    for (name, value) in fields {
+        map.serialize_entry(name, value)?;
+    }
+    map.end()
+}
@@ -0,0 +1,214 @@
+use std::{collections::HashSet, str::FromStr};
+
+use colored::Colorize;
+use ore_api::{consts::TOKEN_DECIMALS, state::Proof};
+use ore_utils::{AccountDeserialize, Discriminator};
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_program::pubkey::Pubkey;
+use spl_token::amount_to_ui_amount;
+
+use crate::{
+    args::{LeaderboardArgs, LeaderboardSort},
+    output::CommandOutput,
+    Miner,
+};
+
+#[derive(Debug, Serialize)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub authority: String,
+    pub proof: String,
+    pub balance: f64,
+    pub total_rewards: f64,
+    pub total_hashes: u64,
+    pub last_hash_at: i64,
+    pub ours: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaderboardOutput {
+    pub sort: String,
+    pub total_miners: usize,
+    pub active_miners: usize,
+    pub active_minutes: u64,
+    pub total_balance: f64,
+    pub total_rewards: f64,
+    pub total_hashes: u64,
+    /// The top proofs, followed by any of our own proofs ranked below them.
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl CommandOutput for LeaderboardOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Miners: {} ({} active in the last {} min)",
+                self.total_miners, self.active_miners, self.active_minutes
+            ),
+            format!("Total staked: {} ORE", self.total_balance),
+            format!("Total rewards: {} ORE", self.total_rewards),
+            format!("Total hashes: {}", self.total_hashes),
+            String::new(),
+            format!("Top proofs by {}:", self.sort),
+        ];
+        for entry in self.entries.iter() {
+            let line = format!(
+                "{:>5}. {}  {} ORE staked  {} ORE earned  {} hashes",
+                entry.rank, entry.authority, entry.balance, entry.total_rewards, entry.total_hashes
+            );
+            if entry.ours {
+                lines.push(line.bold().green().to_string());
+            } else {
+                lines.push(line);
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        (
+            vec![
+                "Rank",
+                "Authority",
+                "Balance (ORE)",
+                "Total rewards (ORE)",
+                "Total hashes",
+                "Last hash at",
+                "Ours",
+            ],
+            self.entries
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.rank.to_string(),
+                        entry.authority.clone(),
+                        entry.balance.to_string(),
+                        entry.total_rewards.to_string(),
+                        entry.total_hashes.to_string(),
+                        entry.last_hash_at.to_string(),
+                        if entry.ours {
+                            "*".into()
+                        } else {
+                            String::new()
+                        },
+                    ]
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Miner {
+    pub async fn leaderboard(&self, args: LeaderboardArgs) {
+        // Resolve the authorities to highlight
+        let ours: HashSet<Pubkey> = if args.authorities.is_empty() {
+            // Highlight nothing when no keypair is available
+            self.try_signer()
+                .map(|signer| signer.pubkey())
+                .into_iter()
+                .collect()
+        } else {
+            let mut ours = HashSet::new();
+            for authority in args.authorities.iter() {
+                let Ok(authority) = Pubkey::from_str(authority) else {
+                    println!("Invalid address: {:?}", authority);
+                    return;
+                };
+                ours.insert(authority);
+            }
+            ours
+        };
+
+        // Fetch every proof account
+        let mut proofs = match self.get_proofs().await {
+            Ok(proofs) => proofs,
+            Err(err) => {
+                println!("Failed to fetch proof accounts: {}", err);
+                return;
+            }
+        };
+
+        // Aggregate network stats
+        let active_since =
+            chrono::Utc::now().timestamp() - (args.active_minutes as i64).saturating_mul(60);
+        let active_miners = proofs
+            .iter()
+            .filter(|(_, proof)| proof.last_hash_at >= active_since)
+            .count();
+        let total_balance: u64 = proofs.iter().map(|(_, proof)| proof.balance).sum();
+        let total_rewards: u64 = proofs.iter().map(|(_, proof)| proof.total_rewards).sum();
+        let total_hashes: u64 = proofs.iter().map(|(_, proof)| proof.total_hashes).sum();
+
+        // Rank proofs, keeping our own even if they fall outside the top
+        proofs.sort_by_key(|(_, proof)| {
+            std::cmp::Reverse(match args.sort {
+                LeaderboardSort::Balance => proof.balance,
+                LeaderboardSort::Rewards => proof.total_rewards,
+                LeaderboardSort::Hashes => proof.total_hashes,
+            })
+        });
+        let entries = proofs
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, proof))| *i < args.limit || ours.contains(&proof.authority))
+            .map(|(i, (address, proof))| LeaderboardEntry {
+                rank: i + 1,
+                authority: proof.authority.to_string(),
+                proof: address.to_string(),
+                balance: amount_to_ui_amount(proof.balance, TOKEN_DECIMALS),
+                total_rewards: amount_to_ui_amount(proof.total_rewards, TOKEN_DECIMALS),
+                total_hashes: proof.total_hashes,
+                last_hash_at: proof.last_hash_at,
+                ours: ours.contains(&proof.authority),
+            })
+            .collect();
+
+        self.print_output(&LeaderboardOutput {
+            sort: format!("{:?}", args.sort).to_lowercase(),
+            total_miners: proofs.len(),
+            active_miners,
+            active_minutes: args.active_minutes,
+            total_balance: amount_to_ui_amount(total_balance, TOKEN_DECIMALS),
+            total_rewards: amount_to_ui_amount(total_rewards, TOKEN_DECIMALS),
+            total_hashes,
+            entries,
+        });
+    }
+
+    /// Fetches every proof account owned by the program.
+    async fn get_proofs(&self) -> Result<Vec<(Pubkey, Proof)>, Box<dyn std::error::Error>> {
+        let accounts = self
+            .rpc_client
+            .get_program_accounts_with_config(
+                &ore_api::ID,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![
+                        RpcFilterType::DataSize(8 + std::mem::size_of::<Proof>() as u64),
+                        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                            0,
+                            Proof::discriminator().to_le_bytes().to_vec(),
+                        )),
+                    ]),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(address, account)| {
+                Proof::try_from_bytes(&account.data)
+                    .ok()
+                    .map(|proof| (address, *proof))
+            })
+            .collect())
+    }
+}
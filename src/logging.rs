@@ -0,0 +1,72 @@
+use std::{
+    fs::OpenOptions,
+    io::IsTerminal,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use clap::ValueEnum;
+use tracing_subscriber::EnvFilter;
+
+use crate::tui::TuiLogWriter;
+
+static SPINNERS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line
+    Json,
+}
+
+/// Installs the global tracing subscriber.
+///
+/// Levels are read from `RUST_LOG` and default to `info`. Logs go to `log_file` when set, to
+/// the dashboard when `tui` is set, and to stderr otherwise, keeping stdout for command
+/// output. Spinners are disabled unless stdout is a terminal, logs are pretty and the
+/// dashboard is off.
+pub fn init_logging(format: LogFormat, log_file: Option<&str>, tui: bool) {
+    let stdout_is_tty = std::io::stdout().is_terminal();
+    SPINNERS_ENABLED.store(
+        stdout_is_tty && format == LogFormat::Pretty && !tui,
+        Ordering::Relaxed,
+    );
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    let file = log_file.map(|path| {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap_or_else(|err| {
+                eprintln!("error: Could not open log file `{}`: {}", path, err);
+                std::process::exit(1);
+            })
+    });
+    match (format, file) {
+        (LogFormat::Pretty, Some(file)) => builder
+            .with_ansi(false)
+            .with_writer(Mutex::new(file))
+            .init(),
+        (LogFormat::Pretty, None) if tui => {
+            builder.with_ansi(false).with_writer(|| TuiLogWriter).init()
+        }
+        (LogFormat::Pretty, None) => builder
+            .with_ansi(std::io::stderr().is_terminal())
+            .with_writer(std::io::stderr)
+            .init(),
+        (LogFormat::Json, Some(file)) => builder.json().with_writer(Mutex::new(file)).init(),
+        (LogFormat::Json, None) if tui => builder.json().with_writer(|| TuiLogWriter).init(),
+        (LogFormat::Json, None) => builder.json().with_writer(std::io::stderr).init(),
+    }
+}
+
+/// Returns whether progress spinners should be drawn.
+pub fn spinners_enabled() -> bool {
+    SPINNERS_ENABLED.load(Ordering::Relaxed)
+}
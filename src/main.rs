@@ -1,34 +1,58 @@
 mod args;
+mod auto_policy;
 mod balance;
 mod benchmark;
+mod benchmark_sweep;
+mod benchmark_verify;
 mod busses;
 mod claim;
 mod close;
 mod config;
 mod cu_limits;
+mod dry_run;
 mod dynamic_fee;
+mod estimate;
 #[cfg(feature = "admin")]
 mod initialize;
+mod inspect;
+mod leaderboard;
+mod logging;
+mod metrics;
 mod mine;
 mod mining_history;
 mod open;
+mod output;
 mod pool;
 mod pool_client;
+mod progress;
 mod proof;
 mod rewards;
 mod send_and_confirm;
+mod shutdown;
+mod sign_only;
+mod signer;
 mod stake;
+mod status;
+mod submit_signed;
+mod tui;
 mod upgrade;
 mod utils;
+mod webhooks;
 
-use std::sync::Arc;
+use std::{
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
 
 use args::*;
 use clap::{command, Parser, Subcommand};
+use logging::LogFormat;
+use output::OutputFormat;
+use signer::{is_stdin_uri, signer_from_uri, MinerSigner};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
-    commitment_config::CommitmentConfig,
-    signature::{read_keypair_file, Keypair},
+    commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey,
+    signer::null_signer::NullSigner,
 };
 
 #[derive(Clone)]
@@ -39,6 +63,15 @@ struct Miner {
     pub dynamic_fee: bool,
     pub rpc_client: Arc<RpcClient>,
     pub fee_payer_filepath: Option<String>,
+    pub output: OutputFormat,
+    pub dry_run: bool,
+    pub assume_yes: bool,
+    pub sign_only: bool,
+    pub blockhash: Option<Hash>,
+    pub min_balance: f64,
+    pub warn_balance: f64,
+    signer: Arc<OnceLock<MinerSigner>>,
+    fee_payer: Arc<OnceLock<MinerSigner>>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -61,9 +94,21 @@ enum Commands {
     #[command(about = "Fetch the program config")]
     Config(ConfigArgs),
 
+    #[command(about = "Estimate mining earnings from your hashpower and the reward rates")]
+    Estimate(EstimateArgs),
+
+    #[command(about = "Fetch any ORE or token account and print its decoded fields")]
+    Inspect(InspectArgs),
+
+    #[command(about = "Rank proofs across the network and summarize miner activity")]
+    Leaderboard(LeaderboardArgs),
+
     #[command(about = "Start mining")]
     Mine(MineArgs),
 
+    #[command(about = "Open a proof account to start mining")]
+    Open(OpenArgs),
+
     #[command(about = "Fetch a proof account by address")]
     Proof(ProofArgs),
 
@@ -73,6 +118,9 @@ enum Commands {
     #[command(about = "Stake to earn a rewards multiplier")]
     Stake(StakeArgs),
 
+    #[command(about = "Combine and submit transactions exported with --sign-only")]
+    SubmitSigned(SubmitSignedArgs),
+
     #[command(about = "Upgrade your ORE tokens from v1 to v2")]
     Upgrade(UpgradeArgs),
 
@@ -81,6 +129,22 @@ enum Commands {
     Initialize(InitializeArgs),
 }
 
+impl Commands {
+    /// Returns whether the command signs with the authority keypair and with the fee payer.
+    fn signers(&self) -> (bool, bool) {
+        match self {
+            Commands::Claim(_) | Commands::Close(_) | Commands::Open(_) | Commands::Upgrade(_) => {
+                (true, true)
+            }
+            Commands::Mine(args) => (!args.solo || args.keypairs.is_empty(), true),
+            Commands::Stake(args) => (!args.plan, !args.plan),
+            #[cfg(feature = "admin")]
+            Commands::Initialize(_) => (true, true),
+            _ => (false, false),
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(about, version)]
 struct Args {
@@ -103,16 +167,16 @@ struct Args {
 
     #[arg(
         long,
-        value_name = "KEYPAIR_FILEPATH",
-        help = "Filepath to keypair to use.",
+        value_name = "KEYPAIR",
+        help = "Keypair to use: a filepath, prompt://, stdin or env://VAR. With --sign-only, also a pubkey whose signature is added elsewhere.",
         global = true
     )]
     keypair: Option<String>,
 
     #[arg(
         long,
-        value_name = "FEE_PAYER_FILEPATH",
-        help = "Filepath to keypair to use as transaction fee payer.",
+        value_name = "FEE_PAYER",
+        help = "Keypair to use as transaction fee payer: a filepath, prompt://, stdin or env://VAR. With --sign-only, also a pubkey whose signature is added elsewhere.",
         global = true
     )]
     fee_payer: Option<String>,
@@ -137,6 +201,83 @@ struct Args {
     #[arg(long, help = "Use dynamic priority fees", global = true)]
     dynamic_fee: bool,
 
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        help = "Output format for command results",
+        value_enum,
+        default_value = "text",
+        global = true
+    )]
+    output: OutputFormat,
+
+    #[arg(
+        long,
+        help = "Simulate transactions and print their effects without sending them",
+        global = true
+    )]
+    dry_run: bool,
+
+    #[arg(
+        short = 'y',
+        long = "yes",
+        help = "Skip confirmation prompts and assume yes",
+        global = true
+    )]
+    assume_yes: bool,
+
+    #[arg(
+        long,
+        help = "Sign transactions with the available keypairs and print them instead of sending",
+        global = true
+    )]
+    sign_only: bool,
+
+    #[arg(
+        long,
+        value_name = "BLOCKHASH",
+        help = "Blockhash to sign with when using --sign-only. Fetched from the RPC if omitted.",
+        requires = "sign_only",
+        global = true
+    )]
+    blockhash: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        help = "Format of log lines",
+        value_enum,
+        default_value = "pretty",
+        global = true
+    )]
+    log_format: LogFormat,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Append logs to this file instead of stderr",
+        global = true
+    )]
+    log_file: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "SOL",
+        help = "Refuse to send transactions when the fee payer balance is at or below this amount",
+        default_value = "0.005",
+        global = true
+    )]
+    min_balance: f64,
+
+    #[arg(
+        long,
+        value_name = "SOL",
+        help = "Warn when the fee payer balance is at or below this amount",
+        default_value = "0.05",
+        global = true
+    )]
+    warn_balance: f64,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -145,6 +286,10 @@ struct Args {
 async fn main() {
     let args = Args::parse();
 
+    // Initialize logging
+    let tui = matches!(&args.command, Commands::Mine(mine_args) if mine_args.tui);
+    logging::init_logging(args.log_format, args.log_file.as_deref(), tui);
+
     // Load the config file from custom path, the default path, or use default config values
     let cli_config = if let Some(config_file) = &args.config_file {
         solana_cli_config::Config::load(config_file).unwrap_or_else(|_| {
@@ -157,10 +302,25 @@ async fn main() {
         solana_cli_config::Config::default()
     };
 
+    // Parse the offline signing blockhash
+    let blockhash = args.blockhash.as_ref().map(|blockhash| {
+        Hash::from_str(blockhash).unwrap_or_else(|_| {
+            eprintln!("error: Invalid blockhash `{}`", blockhash);
+            std::process::exit(1);
+        })
+    });
+
     // Initialize miner.
     let cluster = args.rpc.unwrap_or(cli_config.json_rpc_url);
     let default_keypair = args.keypair.unwrap_or(cli_config.keypair_path.clone());
     let fee_payer_filepath = args.fee_payer.unwrap_or(default_keypair.clone());
+    if is_stdin_uri(&default_keypair)
+        && is_stdin_uri(&fee_payer_filepath)
+        && default_keypair != fee_payer_filepath
+    {
+        eprintln!("error: The keypair and fee payer cannot both be read from stdin");
+        std::process::exit(1);
+    }
     let rpc_client = RpcClient::new_with_commitment(cluster, CommitmentConfig::confirmed());
 
     let miner = Arc::new(Miner::new(
@@ -170,8 +330,24 @@ async fn main() {
         args.dynamic_fee_url,
         args.dynamic_fee,
         Some(fee_payer_filepath),
+        args.output,
+        args.dry_run,
+        args.assume_yes,
+        args.sign_only,
+        blockhash,
+        args.min_balance,
+        args.warn_balance,
     ));
 
+    // Load signers up front so a prompt or load error never interrupts a running command
+    let (needs_signer, needs_fee_payer) = args.command.signers();
+    if needs_signer {
+        miner.signer();
+    }
+    if needs_fee_payer {
+        miner.fee_payer();
+    }
+
     // Execute user command.
     match args.command {
         Commands::Balance(args) => {
@@ -180,8 +356,8 @@ async fn main() {
         Commands::Benchmark(args) => {
             miner.benchmark(args).await;
         }
-        Commands::Busses(_) => {
-            miner.busses().await;
+        Commands::Busses(args) => {
+            miner.busses(args).await;
         }
         Commands::Claim(args) => {
             miner.claim(args).await;
@@ -192,18 +368,36 @@ async fn main() {
         Commands::Config(_) => {
             miner.config().await;
         }
+        Commands::Estimate(args) => {
+            miner.estimate(args).await;
+        }
+        Commands::Inspect(args) => {
+            miner.inspect(args).await;
+        }
+        Commands::Leaderboard(args) => {
+            miner.leaderboard(args).await;
+        }
         Commands::Mine(args) => {
-            miner.mine(args).await;
+            if let Err(err) = miner.mine(args).await {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        Commands::Open(args) => {
+            miner.open(args).await;
         }
         Commands::Proof(args) => {
             miner.proof(args).await;
         }
-        Commands::Rewards(_) => {
-            miner.rewards().await;
+        Commands::Rewards(args) => {
+            miner.rewards(args).await;
         }
         Commands::Stake(args) => {
             miner.stake(args).await;
         }
+        Commands::SubmitSigned(args) => {
+            miner.submit_signed(args).await;
+        }
         Commands::Upgrade(args) => {
             miner.upgrade(args).await;
         }
@@ -215,6 +409,7 @@ async fn main() {
 }
 
 impl Miner {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         rpc_client: Arc<RpcClient>,
         priority_fee: Option<u64>,
@@ -222,6 +417,13 @@ impl Miner {
         dynamic_fee_url: Option<String>,
         dynamic_fee: bool,
         fee_payer_filepath: Option<String>,
+        output: OutputFormat,
+        dry_run: bool,
+        assume_yes: bool,
+        sign_only: bool,
+        blockhash: Option<Hash>,
+        min_balance: f64,
+        warn_balance: f64,
     ) -> Self {
         Self {
             rpc_client,
@@ -230,22 +432,79 @@ impl Miner {
             dynamic_fee_url,
             dynamic_fee,
             fee_payer_filepath,
+            output,
+            dry_run,
+            assume_yes,
+            sign_only,
+            blockhash,
+            min_balance,
+            warn_balance,
+            signer: Arc::new(OnceLock::new()),
+            fee_payer: Arc::new(OnceLock::new()),
         }
     }
 
-    pub fn signer(&self) -> Keypair {
-        match self.keypair_filepath.clone() {
-            Some(filepath) => read_keypair_file(filepath.clone())
-                .expect(format!("No keypair found at {}", filepath).as_str()),
-            None => panic!("No keypair provided"),
+    /// Returns the authority signer, loading it on first use.
+    pub fn signer(&self) -> MinerSigner {
+        self.signer
+            .get_or_init(|| {
+                load_signer_or_exit(self.keypair_filepath.as_deref(), "keypair", self.sign_only)
+            })
+            .clone()
+    }
+
+    /// Returns the authority signer if one is configured and loads, without exiting
+    /// otherwise. Never reads the keypair from stdin.
+    pub fn try_signer(&self) -> Option<MinerSigner> {
+        if let Some(signer) = self.signer.get() {
+            return Some(signer.clone());
         }
+        let uri = self.keypair_filepath.as_deref()?;
+        if is_stdin_uri(uri) {
+            return None;
+        }
+        let signer: MinerSigner = Arc::from(signer_from_uri(uri, "keypair").ok()?);
+        Some(self.signer.get_or_init(|| signer).clone())
     }
 
-    pub fn fee_payer(&self) -> Keypair {
-        match self.fee_payer_filepath.clone() {
-            Some(filepath) => read_keypair_file(filepath.clone())
-                .expect(format!("No fee payer keypair found at {}", filepath).as_str()),
-            None => panic!("No fee payer keypair provided"),
+    /// Returns the fee payer signer, loading it on first use. Reuses the authority
+    /// signer when both point at the same keypair.
+    pub fn fee_payer(&self) -> MinerSigner {
+        if self.fee_payer_filepath == self.keypair_filepath {
+            return self.signer();
+        }
+        self.fee_payer
+            .get_or_init(|| {
+                load_signer_or_exit(
+                    self.fee_payer_filepath.as_deref(),
+                    "fee payer keypair",
+                    self.sign_only,
+                )
+            })
+            .clone()
+    }
+}
+
+/// Loads a signer or exits. With `sign_only`, a pubkey stands in for a keypair held
+/// elsewhere and leaves its signature absent.
+fn load_signer_or_exit(uri: Option<&str>, keypair_name: &str, sign_only: bool) -> MinerSigner {
+    let Some(uri) = uri else {
+        eprintln!("error: No {} provided", keypair_name);
+        std::process::exit(1);
+    };
+    if sign_only {
+        if let Ok(pubkey) = Pubkey::from_str(uri) {
+            return Arc::new(NullSigner::new(&pubkey));
+        }
+    }
+    match signer_from_uri(uri, keypair_name) {
+        Ok(signer) => Arc::from(signer),
+        Err(err) => {
+            eprintln!(
+                "error: Failed to load {} from `{}`: {}",
+                keypair_name, uri, err
+            );
+            std::process::exit(1);
         }
     }
 }
@@ -0,0 +1,103 @@
+use std::{net::SocketAddr, sync::OnceLock};
+
+use axum::{http::header, routing::get, Router};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder, TEXT_FORMAT,
+};
+
+/// Prometheus metrics recorded by the miner, served by `serve_metrics`.
+pub struct Metrics {
+    registry: Registry,
+    pub hashes: IntCounter,
+    pub hashrate: IntGauge,
+    pub best_difficulty: IntGauge,
+    pub submissions: IntCounterVec,
+    pub landing_latency: Histogram,
+    pub priority_fee: IntGauge,
+    pub pool_connected: IntGauge,
+    pub validation_queue_depth: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("ore".to_string()), None).unwrap();
+        let hashes = IntCounter::new("hashes_total", "Hashes computed").unwrap();
+        let hashrate = IntGauge::new("hashrate", "Hashes per second in the last round").unwrap();
+        let best_difficulty =
+            IntGauge::new("best_difficulty", "Best difficulty found in the last round").unwrap();
+        let submissions = IntCounterVec::new(
+            Opts::new("submissions_total", "Transactions submitted by result"),
+            &["result"],
+        )
+        .unwrap();
+        let landing_latency = Histogram::with_opts(
+            HistogramOpts::new(
+                "landing_latency_seconds",
+                "Time from first send to confirmation of landed transactions",
+            )
+            .buckets(vec![0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0]),
+        )
+        .unwrap();
+        let priority_fee = IntGauge::new(
+            "priority_fee_microlamports",
+            "Compute unit price of the last landed transaction",
+        )
+        .unwrap();
+        let pool_connected =
+            IntGauge::new("pool_connected", "Whether the pool connection is open").unwrap();
+        let validation_queue_depth = IntGauge::new(
+            "validation_queue_depth",
+            "Pool validation requests waiting to be processed",
+        )
+        .unwrap();
+        registry.register(Box::new(hashes.clone())).unwrap();
+        registry.register(Box::new(hashrate.clone())).unwrap();
+        registry
+            .register(Box::new(best_difficulty.clone()))
+            .unwrap();
+        registry.register(Box::new(submissions.clone())).unwrap();
+        registry
+            .register(Box::new(landing_latency.clone()))
+            .unwrap();
+        registry.register(Box::new(priority_fee.clone())).unwrap();
+        registry.register(Box::new(pool_connected.clone())).unwrap();
+        registry
+            .register(Box::new(validation_queue_depth.clone()))
+            .unwrap();
+        Self {
+            registry,
+            hashes,
+            hashrate,
+            best_difficulty,
+            submissions,
+            landing_latency,
+            priority_fee,
+            pool_connected,
+            validation_queue_depth,
+        }
+    }
+
+    /// Renders all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut buffer = vec![];
+        let _ = TextEncoder::new().encode(&self.registry.gather(), &mut buffer);
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Returns the process-wide metrics. Recording is cheap whether or not they are served.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// Serves the metrics at `http://<addr>/metrics` until the process exits.
+pub async fn serve_metrics(addr: SocketAddr) -> Result<(), std::io::Error> {
+    let app = Router::new().route(
+        "/metrics",
+        get(|| async { ([(header::CONTENT_TYPE, TEXT_FORMAT)], metrics().render()) }),
+    );
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await
+}
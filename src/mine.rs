@@ -1,39 +1,155 @@
 use std::{
-    sync::{Arc, atomic::{AtomicBool, Ordering}},
+    collections::HashMap,
+    sync::{Arc, atomic::{AtomicBool, AtomicU64, Ordering}},
     time::{Duration, Instant},
 };
 
-use colored::*;
+use tracing::Instrument;
 use drillx::{
     equix::{self},
     Hash, Solution,
 };
-use solana_rpc_client::spinner;
+use solana_program::native_token::lamports_to_sol;
 use solana_sdk::{
+    signature::Signature,
     signer::Signer,
     transaction::Transaction,
     pubkey::Pubkey,
-    signature::Keypair,
 };
 
 use crate::{
-    args::MineArgs,
+    args::{LowBalanceAction, MineArgs, MineSchedule},
+    benchmark_sweep::SweepCache,
+    cu_limits::CU_LIMIT_MINE,
+    load_signer_or_exit,
+    metrics::{metrics, serve_metrics},
     mining_history::{MiningHistory, MiningPattern},
     pool_client::PoolClient,
+    progress::Progress,
+    send_and_confirm::{BalanceLevel, ComputeBudget},
+    shutdown::install_shutdown_handler,
+    signer::MinerSigner,
+    status::{serve_status, status, ChallengeStatus, SessionSummary},
+    tui::run_tui,
+    webhooks::{
+        configure_webhooks, difficulty_threshold, flush_webhooks, notify,
+        record_transaction_result, WebhookEvent,
+    },
     utils,
     Miner,
 };
 
+/// Seconds between balance checks while mining is paused for funds.
+const BALANCE_POLL_SECS: u64 = 30;
+
+/// Seconds between checks for a resume while mining is paused through the status API.
+const PAUSE_POLL_SECS: u64 = 1;
+
+/// Whether queued solutions can be submitted, per the `--low-balance` action.
+#[derive(Debug, PartialEq, Eq)]
+enum Funds {
+    Available,
+    Held,
+    Stop,
+}
+
 impl Miner {
     pub async fn mine(&self, args: MineArgs) -> Result<(), Box<dyn std::error::Error>> {
-        // Get signer
-        let signer = self.signer();
+        // Get signers
+        let authorities = self.mining_authorities(&args.keypairs);
+        if authorities.len() > 1 {
+            tracing::info!("Mining with {} keypairs", authorities.len());
+        }
+
+        // Claiming into a mining wallet while auto-staking would stake the rewards straight
+        // back, paying fees on every round trip
+        if args.auto_claim_threshold.is_some() && args.auto_stake_interval.is_some() {
+            let claims_to_authority = match &args.auto_claim_to {
+                None => true,
+                Some(to) => authorities
+                    .iter()
+                    .any(|authority| authority.pubkey().to_string() == *to),
+            };
+            if claims_to_authority {
+                return Err("--auto-stake-interval would restake automatically claimed rewards. \
+                    Pass --auto-claim-to with a wallet that does not mine"
+                    .into());
+            }
+        }
+
+        // Open proofs that do not exist yet
+        for authority in authorities.iter() {
+            if let Err(err) = self.open_if_missing(authority).await {
+                return Err(format!("Failed to open proof for {}: {}", authority.pubkey(), err).into());
+            }
+        }
+
+        // Report how long the fee payer balance will last
+        if !self.dry_run && !self.sign_only {
+            if let Ok(check) = self.check_balance(CU_LIMIT_MINE).await {
+                tracing::info!(
+                    "Fee payer balance {} SOL covers about {} mine transactions",
+                    lamports_to_sol(check.balance),
+                    check.transactions_remaining
+                );
+            }
+        }
 
         // Check num threads
-        self.check_num_cores(args.cores);
+        let cores = args
+            .cores
+            .or_else(|| SweepCache::load().map(|sweep| sweep.recommended_cores))
+            .unwrap_or(1);
+        self.check_num_cores(cores);
 
         // Load mining history
         let mut history = MiningHistory::new();
+        let mut last_stake_at = Instant::now();
+
+        // Serve metrics
+        if let Some(addr) = args.metrics_addr {
+            tokio::spawn(async move {
+                if let Err(e) = serve_metrics(addr).await {
+                    tracing::error!("Failed to serve metrics on {}: {}", addr, e);
+                }
+            });
+        }
+
+        // Serve status and pause controls
+        if let Some(addr) = args.status_addr {
+            tokio::spawn(async move {
+                if let Err(e) = serve_status(addr).await {
+                    tracing::error!("Failed to serve status on {}: {}", addr, e);
+                }
+            });
+        }
+
+        // Configure webhooks
+        configure_webhooks(
+            args.webhook_urls.clone(),
+            args.webhook_difficulty,
+            args.webhook_failure_streak,
+        );
+
+        // Stop on Ctrl-C or SIGTERM
+        let shutdown = install_shutdown_handler();
+        let mut session = SessionSummary::new();
+        status().set_min_difficulty(args.min_difficulty);
+        let config = utils::get_config(&self.rpc_client).await;
+        status().set_min_difficulty_floor(config.min_difficulty as u32);
+        status().init_cores(cores as usize);
+
+        // Show the dashboard
+        let tui_handle = if args.tui {
+            let shutdown = shutdown.clone();
+            Some(std::thread::spawn(move || {
+                if let Err(e) = run_tui(shutdown) {
+                    tracing::error!("Dashboard failed: {}", e);
+                }
+            }))
+        } else {
+            None
+        };
 
         // Create and connect pool client
         let miner_arc = Arc::new(self.clone());
@@ -41,155 +157,330 @@ impl Miner {
             args.pool_url.clone(),
             miner_arc,
         ));
-        let pool_clone = pool_client.clone();
-        tokio::spawn(async move {
-            if let Err(e) = pool_clone.connect().await {
-                println!("Failed to connect to pool: {}", e);
-            }
-        });
+        let pool_handle = if !args.solo {
+            let pool_clone = pool_client.clone();
+            let pool_url = args.pool_url.clone();
+            let shutdown = shutdown.clone();
+            Some(tokio::spawn(async move {
+                let result = pool_clone.connect().await;
+                if let Err(e) = &result {
+                    tracing::error!("Failed to connect to pool: {}", e);
+                }
+
+                // Report connections that close before shutdown
+                if !shutdown.load(Ordering::Relaxed) {
+                    notify(WebhookEvent::PoolDisconnected {
+                        url: pool_url,
+                        error: result.err().map(|e| e.to_string()),
+                    });
+                }
+            }))
+        } else {
+            None
+        };
 
         // Start mining loop
-        loop {
-            // Fetch proof for challenge
-            let proof = utils::get_updated_proof_with_authority(&self.rpc_client, signer.pubkey(), 0).await.unwrap();
-            
-            // Run drillx with time limit
-            let (solution, hash, difficulty, nonce_range) = Self::find_hash_par(
-                args.cores,
-                args.min_difficulty,
-                proof.challenge,
-                args.time_limit,
-            ).await;
-
-            // Add successful pattern to history if difficulty meets target
-            if difficulty >= args.min_difficulty {
-                history.add_pattern(MiningPattern {
-                    challenge: hash.h.to_vec(),
-                    nonce_range,
-                    difficulty,
-                });
-
-                // Submit hash to pool
-                if let Err(e) = pool_client.submit_hash(
-                    bs58::encode(hash.h).into_string(),
-                    difficulty,
-                    hex::encode(solution.n),
-                ).await {
-                    println!("Failed to submit hash to pool: {}", e);
+        let mut queued: HashMap<Pubkey, (MinerSigner, Solution, u32)> = HashMap::new();
+        while !shutdown.load(Ordering::Relaxed) {
+            // Skip rounds and their RPC calls while paused through the status API
+            if status().is_paused() {
+                tokio::time::sleep(Duration::from_secs(PAUSE_POLL_SECS)).await;
+                continue;
+            }
+            session.rounds += 1;
+            let round = tracing::info_span!("round", number = session.rounds);
+            let stop = async {
+                // Fetch proofs for challenges, earliest cutoff first
+                let mut jobs = vec![];
+                for authority in authorities.iter() {
+                    let proof = utils::get_updated_proof_with_authority(&self.rpc_client, authority.pubkey(), 0).await.unwrap();
+                    jobs.push((authority.clone(), proof.challenge, proof.last_hash_at));
+                }
+                jobs.sort_by_key(|(_, _, last_hash_at)| *last_hash_at);
+                status().start_round(
+                    session.rounds,
+                    jobs.iter()
+                        .map(|(authority, challenge, last_hash_at)| ChallengeStatus {
+                            authority: authority.pubkey().to_string(),
+                            challenge: bs58::encode(challenge).into_string(),
+                            time_limit: Self::get_time_limit(*last_hash_at, &args),
+                        })
+                        .collect(),
+                );
+
+                // Run drillx with time limit, sharing the cores across proofs
+                let min_difficulty = status().min_difficulty();
+                let batch_size = match args.schedule {
+                    MineSchedule::Sequential => 1,
+                    MineSchedule::Parallel => cores.max(1) as usize,
+                };
+                let mut solutions = vec![];
+                for batch in jobs.chunks(batch_size) {
+                    // Measure time limits as each batch starts, since earlier batches use up the round
+                    let results = Self::find_hash_par(
+                        cores,
+                        min_difficulty,
+                        batch
+                            .iter()
+                            .map(|(_, challenge, last_hash_at)| {
+                                (*challenge, Self::get_time_limit(*last_hash_at, &args))
+                            })
+                            .collect(),
+                        shutdown.clone(),
+                    ).await;
+
+                    // Drop partial results once shutdown is requested
+                    if shutdown.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    for ((authority, _, _), (solution, hash, difficulty, nonce_range)) in batch.iter().zip(results) {
+                        // Report new session bests above the webhook threshold
+                        if difficulty > session.best_difficulty
+                            && difficulty_threshold().map_or(false, |threshold| difficulty >= threshold)
+                        {
+                            notify(WebhookEvent::BestDifficulty {
+                                authority: authority.pubkey().to_string(),
+                                difficulty,
+                                hash: bs58::encode(hash.h).into_string(),
+                            });
+                        }
+                        session.best_difficulty = session.best_difficulty.max(difficulty);
+
+                        // Add successful pattern to history if difficulty meets target
+                        if difficulty >= min_difficulty {
+                            session.solutions += 1;
+                            history.add_pattern(MiningPattern {
+                                challenge: hash.h.to_vec(),
+                                nonce_range,
+                                difficulty,
+                            });
+
+                            // Queue solution for on-chain submission
+                            if args.solo {
+                                solutions.push((authority.clone(), solution, difficulty));
+                                continue;
+                            }
+
+                            // Submit hash to pool
+                            if let Err(e) = pool_client.submit_hash(
+                                bs58::encode(hash.h).into_string(),
+                                difficulty,
+                                hex::encode(solution.n),
+                                authority.pubkey().to_string(),
+                            ).await {
+                                tracing::warn!("Failed to submit hash to pool: {}", e);
+                            } else {
+                                session.submitted += 1;
+                                tracing::info!(difficulty, "Submitted hash to pool for {}", authority.pubkey());
+                            }
+                        }
+                    }
+                }
+
+                if shutdown.load(Ordering::Relaxed) {
+                    return true;
+                }
+
+                // Hold solutions while the fee payer cannot cover them
+                for (authority, solution, difficulty) in solutions {
+                    let better = queued
+                        .get(&authority.pubkey())
+                        .map_or(true, |(_, _, queued_difficulty)| difficulty > *queued_difficulty);
+                    if better {
+                        queued.insert(authority.pubkey(), (authority, solution, difficulty));
+                    }
+                }
+                let funds = if queued.is_empty() || self.dry_run || self.sign_only {
+                    Funds::Available
                 } else {
-                    println!("Successfully submitted hash to pool");
+                    let cu_limit = CU_LIMIT_MINE.saturating_mul(queued.len() as u32);
+                    self.wait_for_balance(cu_limit, args.low_balance, &shutdown).await
+                };
+                if funds == Funds::Stop {
+                    return true;
                 }
-            }
 
-            // Handle validation requests if we're the validator
-            if let Some(validation_request) = pool_client.get_validation_request().await {
-                println!("Validating hash: {}", validation_request.hash);
-                
-                // Create transaction
-                let result = async {
-                    let miner_pubkey = Pubkey::try_from(validation_request.minerAddress.as_str())?;
-                    let bus = utils::find_available_bus(&self.rpc_client).await?;
-                    let hash_data = bs58::decode(&validation_request.hash).into_vec()?;
-                    let nonce_data = hex::decode(&validation_request.nonce)?;
-                    
-                    let tx = Transaction::new_with_payer(
-                        &[utils::create_mine_ix(
-                            miner_pubkey,
-                            self.signer().pubkey(),
-                            bus,
-                            hash_data,
-                            nonce_data,
-                        )],
-                        Some(&self.signer().pubkey()),
-                    );
+                // Submit every proof's solution through the fee payer
+                if !queued.is_empty() && funds == Funds::Available {
+                    let solutions = queued
+                        .drain()
+                        .map(|(_, (authority, solution, _))| (authority, solution))
+                        .collect();
+                    session.submitted += self.submit_solutions(solutions).await;
+                }
+                if shutdown.load(Ordering::Relaxed) {
+                    return true;
+                }
+
+                // Claim and stake automatically
+                self.run_auto_policies(&authorities, &args, &mut history, &mut last_stake_at).await;
+
+                // Handle validation requests if we're the validator
+                if let Some(validation_request) = pool_client.get_validation_request().await {
+                    tracing::info!(hash_id = %validation_request.hashId, "Validating hash: {}", validation_request.hash);
+
+                    // Create transaction
+                    let result = async {
+                        let miner_pubkey = Pubkey::try_from(validation_request.minerAddress.as_str())?;
+                        let bus = utils::find_bus(&self.rpc_client).await;
+                        let hash_data = bs58::decode(&validation_request.hash).into_vec()?;
+                        let nonce_data = hex::decode(&validation_request.nonce)?;
 
-                    Ok::<Transaction, Box<dyn std::error::Error>>(tx)
-                }.await;
-
-                match result {
-                    Ok(tx) => {
-                        let signer = self.signer() as &dyn Signer;
-                        match utils::send_and_confirm_transaction(&self.rpc_client, tx, &[signer]).await {
-                            Ok(signature) => {
-                                if let Err(e) = pool_client.submit_validation_result(
-                                    validation_request.hashId,
-                                    true,
-                                    Some(signature.to_string()),
-                                    None,
-                                ).await {
-                                    println!("Failed to submit validation result: {}", e);
+                        let tx = Transaction::new_with_payer(
+                            &[utils::create_mine_ix(
+                                miner_pubkey,
+                                self.signer().pubkey(),
+                                bus,
+                                hash_data,
+                                nonce_data,
+                            )],
+                            Some(&self.fee_payer().pubkey()),
+                        );
+
+                        Ok::<Transaction, Box<dyn std::error::Error>>(tx)
+                    }.await;
+
+                    match result {
+                        Ok(tx) => {
+                            // Validation transactions are paid by the fee payer like solo submissions
+                            let funds = if self.dry_run || self.sign_only {
+                                Funds::Available
+                            } else {
+                                self.wait_for_balance(CU_LIMIT_MINE, args.low_balance, &shutdown).await
+                            };
+                            let sent = match funds {
+                                Funds::Available => self.send_validation_tx(tx).await,
+                                Funds::Held | Funds::Stop => Err("Insufficient fee payer balance".into()),
+                            };
+                            match sent {
+                                Ok(signature) => {
+                                    if let Err(e) = pool_client.submit_validation_result(
+                                        validation_request.hashId,
+                                        true,
+                                        Some(signature.to_string()),
+                                        None,
+                                    ).await {
+                                        tracing::warn!("Failed to submit validation result: {}", e);
+                                    }
                                 }
-                            }
-                            Err(e) => {
-                                if let Err(e) = pool_client.submit_validation_result(
-                                    validation_request.hashId,
-                                    false,
-                                    None,
-                                    Some(e.to_string()),
-                                ).await {
-                                    println!("Failed to submit validation result: {}", e);
+                                Err(e) => {
+                                    if let Err(e) = pool_client.submit_validation_result(
+                                        validation_request.hashId,
+                                        false,
+                                        None,
+                                        Some(e.to_string()),
+                                    ).await {
+                                        tracing::warn!("Failed to submit validation result: {}", e);
+                                    }
                                 }
                             }
+                            if funds == Funds::Stop {
+                                return true;
+                            }
                         }
-                    }
-                    Err(e) => {
-                        if let Err(e) = pool_client.submit_validation_result(
-                            validation_request.hashId,
-                            false,
-                            None,
-                            Some(e.to_string()),
-                        ).await {
-                            println!("Failed to submit validation result: {}", e);
+                        Err(e) => {
+                            if let Err(e) = pool_client.submit_validation_result(
+                                validation_request.hashId,
+                                false,
+                                None,
+                                Some(e.to_string()),
+                            ).await {
+                                tracing::warn!("Failed to submit validation result: {}", e);
+                            }
                         }
                     }
                 }
+
+                false
             }
+            .instrument(round)
+            .await;
+            status().set_session(&session);
+            if stop {
+                break;
+            }
+        }
+
+        // Flush state and close the pool connection before exiting
+        history.flush();
+        if let Some(pool_handle) = pool_handle {
+            pool_client.close();
+            let _ = tokio::time::timeout(Duration::from_secs(5), pool_handle).await;
         }
+        flush_webhooks(Duration::from_secs(5)).await;
+
+        // Close the dashboard so the summary is printed to the normal screen
+        if let Some(tui_handle) = tui_handle {
+            shutdown.store(true, Ordering::Relaxed);
+            let _ = tui_handle.join();
+        }
+        session.print();
+        Ok(())
     }
 
+    /// Hashes each challenge until its time limit, splitting the cores across
+    /// challenges so that one set of threads serves every proof.
     async fn find_hash_par(
         cores: u64,
         min_difficulty: u32,
-        challenge: [u8; 32],
-        time_limit: u64,
-    ) -> (Solution, Hash, u32, (u64, u64)) {
+        jobs: Vec<([u8; 32], u64)>,
+        shutdown: Arc<AtomicBool>,
+    ) -> Vec<(Solution, Hash, u32, (u64, u64))> {
         // Dispatch job to each thread
-        let stop_flag = Arc::new(AtomicBool::new(false));
-        let progress_bar = Arc::new(spinner::new_progress_bar());
-        progress_bar.set_message("Mining...");
+        let num_jobs = jobs.len().max(1) as u64;
+        let stop_flags: Vec<_> = jobs.iter().map(|_| Arc::new(AtomicBool::new(false))).collect();
+        let progress_bar = Arc::new(Progress::new());
+        progress_bar.set_message("Mining...".to_string());
+        let round_start = Instant::now();
+        let round_hashes = Arc::new(AtomicU64::new(0));
         let core_ids = core_affinity::get_core_ids().unwrap();
         let handles: Vec<_> = core_ids
             .into_iter()
             .map(|i| {
                 let progress_bar = progress_bar.clone();
-                let stop_flag = stop_flag.clone();
-                let challenge = challenge;
-                let time_limit = time_limit;
+                let job = (i.id as u64 % num_jobs) as usize;
+                let stop_flag = stop_flags[job].clone();
+                let shutdown = shutdown.clone();
+                let round_hashes = round_hashes.clone();
+                let (challenge, time_limit) = jobs[job];
+
+                // Threads assigned to the same challenge split its nonce space
+                let job_threads = (cores.saturating_sub(job as u64) + num_jobs - 1) / num_jobs;
+                let job_thread = i.id as u64 / num_jobs;
                 std::thread::spawn(move || {
                     let mut memory = equix::SolverMemory::new();
                     // Return if core should not be used
                     if (i.id as u64).ge(&cores) {
-                        return (0u64, 0u32, Hash::default(), (0u64, 0u64));
+                        return (job, 0u64, 0u32, Hash::default(), (0u64, 0u64));
                     }
 
                     // Pin to core
                     let _ = core_affinity::set_for_current(i);
 
                     // Initialize mining variables
-                    let mut nonce = u64::MAX.saturating_div(cores).saturating_mul(i.id as u64);
+                    let mut nonce = u64::MAX.saturating_div(job_threads.max(1)).saturating_mul(job_thread);
                     let start_nonce = nonce;
                     let mut best_nonce = nonce;
                     let mut best_difficulty = 0;
                     let mut best_hash = Hash::default();
+                    let mut histogram = vec![0u64; 64];
 
                     let start_time = Instant::now();
 
                     // Start hashing
                     loop {
                         // Check if stop flag is set or time limit reached
-                        if stop_flag.load(Ordering::Relaxed) || start_time.elapsed() >= Duration::from_secs(time_limit) {
+                        if stop_flag.load(Ordering::Relaxed) || shutdown.load(Ordering::Relaxed) || start_time.elapsed() >= Duration::from_secs(time_limit) {
                             break;
                         }
+
+                        // Idle while paused through the status API
+                        if status().is_paused() {
+                            std::thread::sleep(Duration::from_millis(100));
+                            continue;
+                        }
+
                         // Create hash
                         if let Ok(hx) = drillx::hash_with_memory(
                             &mut memory,
@@ -197,14 +488,21 @@ impl Miner {
                             &nonce.to_le_bytes(),
                         ) {
                             let difficulty = hx.difficulty();
+                            histogram[(difficulty as usize).min(63)] += 1;
                             if difficulty.gt(&best_difficulty) {
                                 best_nonce = nonce;
                                 best_difficulty = difficulty;
                                 best_hash = hx;
+                                status().record_difficulty(difficulty);
                             }
                             
                             // Update progress every 1000 hashes
                             if nonce % 1000 == 0 {
+                                let elapsed = start_time.elapsed().as_secs_f64().max(f64::EPSILON);
+                                let hashrate = nonce.saturating_sub(start_nonce) as f64 / elapsed;
+                                status().set_core_hashrate(i.id, hashrate as u64);
+                                status().add_histogram(&histogram);
+                                histogram.iter_mut().for_each(|count| *count = 0);
                                 progress_bar.set_message(format!(
                                     "Current: {} | Best: {} | Target: {} | Time: {:?} | Mining...",
                                     difficulty,
@@ -222,41 +520,198 @@ impl Miner {
                         }
                         nonce += 1;
                     }
+
+                    // Record hashes for metrics
+                    status().add_histogram(&histogram);
+                    let hashes = nonce.saturating_sub(start_nonce);
+                    round_hashes.fetch_add(hashes, Ordering::Relaxed);
+                    metrics().hashes.inc_by(hashes);
+
                     // Return the best result
-                    (best_nonce, best_difficulty, best_hash, (start_nonce, best_nonce))
+                    (job, best_nonce, best_difficulty, best_hash, (start_nonce, best_nonce))
                 })
             })
             .collect();
 
-        // Join handles and return best nonce
-        let mut best_result = (0u64, 0u32, Hash::default(), (0u64, 0u64));
+        // Join handles and return best nonce per challenge
+        let mut best_results: Vec<_> = (0..jobs.len())
+            .map(|_| (0u64, 0u32, Hash::default(), (0u64, 0u64)))
+            .collect();
         for h in handles {
-            if let Ok(result) = h.join() {
-                if result.1 > best_result.1 {
-                    best_result = result;
+            if let Ok((job, nonce, difficulty, hash, nonce_range)) = h.join() {
+                if difficulty > best_results[job].1 {
+                    best_results[job] = (nonce, difficulty, hash, nonce_range);
                 }
             }
         }
 
-        // Update log
+        // Update log and metrics
+        let (best_difficulty, best_hash) = best_results
+            .iter()
+            .max_by_key(|r| r.1)
+            .map_or((0, String::new()), |r| (r.1, bs58::encode(r.2.h).into_string()));
+        let elapsed = round_start.elapsed().as_secs_f64().max(f64::EPSILON);
+        metrics().hashrate.set((round_hashes.load(Ordering::Relaxed) as f64 / elapsed) as i64);
+        metrics().best_difficulty.set(best_difficulty as i64);
         progress_bar.finish_with_message(format!(
             "Best hash: {} (difficulty: {})",
-            bs58::encode(best_result.2.h).into_string(),
-            best_result.1
+            best_hash,
+            best_difficulty
         ));
 
-        let solution = Solution::new(best_result.2.d, best_result.0.to_le_bytes());
-        (solution, best_result.2, best_result.1, best_result.3)
+        best_results
+            .into_iter()
+            .map(|(nonce, difficulty, hash, nonce_range)| {
+                let solution = Solution::new(hash.d, nonce.to_le_bytes());
+                (solution, hash, difficulty, nonce_range)
+            })
+            .collect()
+    }
+
+    /// Submits solutions on-chain, packing the mine instructions of every proof into as
+    /// few transactions as possible with the fee payer covering all of them.
+    /// Returns the number of solutions that landed.
+    async fn submit_solutions(&self, solutions: Vec<(MinerSigner, Solution)>) -> u64 {
+        let bus = utils::find_bus(&self.rpc_client).await;
+        let groups = solutions
+            .into_iter()
+            .map(|(authority, solution)| {
+                let ixs = vec![
+                    ore_api::instruction::auth(utils::proof_pubkey(authority.pubkey())),
+                    ore_api::instruction::mine(authority.pubkey(), authority.pubkey(), bus, solution),
+                ];
+                (authority, ixs)
+            })
+            .collect();
+        let mut landed = 0;
+        for (ixs, signers) in self.pack_instructions(groups) {
+            let cu_limit = CU_LIMIT_MINE.saturating_mul(signers.len() as u32);
+            match self
+                .send_and_confirm_with_signers(&ixs, &signers, ComputeBudget::Fixed(cu_limit), false)
+                .await
+            {
+                Ok(Some(_)) => landed += signers.len() as u64,
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to submit mine transaction: {}", e),
+            }
+        }
+        landed
+    }
+
+    /// Signs a pool validation transaction as the validator and sends it, recording
+    /// the result in the submission metrics, the status API and the webhook failure streak.
+    async fn send_validation_tx(&self, tx: Transaction) -> Result<Signature, Box<dyn std::error::Error>> {
+        let validator = self.signer();
+        let fee_payer = self.fee_payer();
+        let mut signers: Vec<&dyn Signer> = vec![&*validator];
+        if fee_payer.pubkey() != validator.pubkey() {
+            signers.push(&*fee_payer);
+        }
+        let sent_at = Instant::now();
+        let result = utils::send_and_confirm_transaction(&self.rpc_client, tx, &signers).await;
+        match &result {
+            Ok(signature) => {
+                metrics().submissions.with_label_values(&["success"]).inc();
+                metrics().landing_latency.observe(sent_at.elapsed().as_secs_f64());
+                status().add_signature(signature.to_string());
+                record_transaction_result(None);
+            }
+            Err(e) => {
+                metrics().submissions.with_label_values(&["failure"]).inc();
+                record_transaction_result(Some(&e.to_string()));
+            }
+        }
+        result
+    }
+
+    /// Applies the `--low-balance` action when the fee payer cannot cover a transaction
+    /// with `cu_limit` compute units.
+    async fn wait_for_balance(
+        &self,
+        cu_limit: u32,
+        action: LowBalanceAction,
+        shutdown: &AtomicBool,
+    ) -> Funds {
+        loop {
+            let check = match self.check_balance(cu_limit).await {
+                Ok(check) => check,
+                Err(e) => {
+                    tracing::warn!("Failed to check fee payer balance: {}", e);
+                    return Funds::Available;
+                }
+            };
+            if check.level != BalanceLevel::Insufficient {
+                return Funds::Available;
+            }
+            match action {
+                LowBalanceAction::Exit => {
+                    tracing::error!("Stopping: fee payer balance is below the minimum");
+                    return Funds::Stop;
+                }
+                LowBalanceAction::Queue => {
+                    tracing::info!(
+                        "Holding solutions until the fee payer covers a {} lamport transaction",
+                        check.fee_per_tx
+                    );
+                    return Funds::Held;
+                }
+                LowBalanceAction::Pause => {
+                    tracing::info!("Paused until the fee payer is topped up");
+                    for _ in 0..BALANCE_POLL_SECS {
+                        if shutdown.load(Ordering::Relaxed) {
+                            return Funds::Stop;
+                        }
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns how long to hash a challenge: the time left before the proof's
+    /// cutoff, capped at the configured time limit.
+    fn get_time_limit(last_hash_at: i64, args: &MineArgs) -> u64 {
+        let now = chrono::Utc::now().timestamp();
+        let cutoff = last_hash_at
+            .saturating_add(60)
+            .saturating_sub(args.buffer_time as i64)
+            .saturating_sub(now);
+        if cutoff > 0 {
+            (cutoff as u64).min(args.time_limit)
+        } else {
+            args.time_limit
+        }
+    }
+
+    /// Loads the signers to mine with from `--keypairs`, expanding directories into
+    /// the keypair files they contain. Falls back to the default keypair.
+    fn mining_authorities(&self, keypairs: &[String]) -> Vec<MinerSigner> {
+        if keypairs.is_empty() {
+            return vec![self.signer()];
+        }
+        let mut uris = vec![];
+        for keypair in keypairs {
+            match std::fs::read_dir(keypair) {
+                Ok(entries) => {
+                    let mut paths: Vec<_> = entries
+                        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+                        .collect();
+                    paths.sort();
+                    uris.extend(paths.iter().map(|path| path.display().to_string()));
+                }
+                Err(_) => uris.push(keypair.clone()),
+            }
+        }
+        uris.iter()
+            .map(|uri| load_signer_or_exit(Some(uri.as_str()), "keypair", false))
+            .collect()
     }
 
     pub fn check_num_cores(&self, cores: u64) {
         let num_cores = num_cpus::get() as u64;
         if cores.gt(&num_cores) {
-            println!(
-                "{} Cannot exceeds available cores ({})",
-                "WARNING".bold().yellow(),
-                num_cores
-            );
+            tracing::warn!("Cannot exceeds available cores ({})", num_cores);
         }
     }
 }
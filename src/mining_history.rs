@@ -8,9 +8,24 @@ pub struct MiningPattern {
     pub difficulty: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferRecord {
+    pub kind: String,
+    pub authority: String,
+    pub amount: u64,
+    pub destination: String,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MiningHistory {
     patterns: Vec<MiningPattern>,
+    #[serde(default)]
+    transfers: Vec<TransferRecord>,
 }
 
 impl MiningHistory {
@@ -22,6 +37,7 @@ impl MiningHistory {
         }
         Self {
             patterns: Vec::new(),
+            transfers: Vec::new(),
         }
     }
 
@@ -30,6 +46,11 @@ impl MiningHistory {
         self.save();
     }
 
+    pub fn add_transfer(&mut self, transfer: TransferRecord) {
+        self.transfers.push(transfer);
+        self.save();
+    }
+
     pub fn get_best_patterns(&self, target_difficulty: u32) -> Vec<&MiningPattern> {
         self.patterns
             .iter()
@@ -37,9 +58,17 @@ impl MiningHistory {
             .collect()
     }
 
+    /// Writes the history to disk. Call before exiting to persist the latest state.
+    pub fn flush(&self) {
+        self.save();
+    }
+
     fn save(&self) {
+        // Write to a temporary file first so an interrupted write never truncates the history
         if let Ok(content) = serde_json::to_string_pretty(self) {
-            let _ = fs::write("mining_history.json", content);
+            if fs::write("mining_history.json.tmp", content).is_ok() {
+                let _ = fs::rename("mining_history.json.tmp", "mining_history.json");
+            }
         }
     }
 }
@@ -1,22 +1,93 @@
-use solana_sdk::signature::Signer;
+use std::str::FromStr;
 
-use crate::{send_and_confirm::ComputeBudget, utils::proof_pubkey, Miner};
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::{
+    args::OpenArgs, send_and_confirm::ComputeBudget, signer::MinerSigner, utils::proof_pubkey,
+    Miner,
+};
 
 impl Miner {
-    pub async fn open(&self) {
-        // Return early if miner is already registered
+    pub async fn open(&self, args: OpenArgs) {
+        // Parse miner address
         let signer = self.signer();
-        let fee_payer = self.fee_payer();
-        let proof_address = proof_pubkey(signer.pubkey());
-        if self.rpc_client.get_account(&proof_address).await.is_ok() {
-            return;
+        let miner = match args.miner {
+            Some(miner) => match Pubkey::from_str(&miner) {
+                Ok(miner) => miner,
+                Err(_) => {
+                    println!("Invalid miner address: {:?}", miner);
+                    return;
+                }
+            },
+            None => signer.pubkey(),
+        };
+
+        // Open proof
+        match self.proof_exists(signer.pubkey()).await {
+            Ok(true) => {
+                println!("Proof already exists: {}", proof_pubkey(signer.pubkey()));
+                return;
+            }
+            Ok(false) => {}
+            Err(err) => {
+                println!("Failed to fetch proof account: {}", err);
+                return;
+            }
+        }
+        if let Err(err) = self.open_proof(&signer, miner).await {
+            println!("Failed to open proof: {}", err);
+        }
+    }
+
+    /// Opens a proof for the authority if one does not exist yet. Fails under `--dry-run`
+    /// and `--sign-only`, where the proof would not actually be created.
+    pub async fn open_if_missing(&self, authority: &MinerSigner) -> ClientResult<()> {
+        if self.proof_exists(authority.pubkey()).await? {
+            return Ok(());
+        }
+        if self.dry_run || self.sign_only {
+            return Err(ClientError {
+                request: None,
+                kind: ClientErrorKind::Custom(format!(
+                    "No proof found for {}. Open one without --dry-run or --sign-only first",
+                    authority.pubkey()
+                )),
+            });
         }
+        tracing::info!("No proof found for {}. Opening one...", authority.pubkey());
+        self.open_proof(authority, authority.pubkey()).await?;
+        Ok(())
+    }
 
+    /// Returns whether the authority's proof account exists. Only a missing account
+    /// counts as no proof; RPC failures are returned as errors.
+    async fn proof_exists(&self, authority: Pubkey) -> ClientResult<bool> {
+        let proof_address = proof_pubkey(authority);
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&proof_address, self.rpc_client.commitment())
+            .await?
+            .value;
+        Ok(account.is_some())
+    }
+
+    async fn open_proof(
+        &self,
+        authority: &MinerSigner,
+        miner: Pubkey,
+    ) -> ClientResult<Option<Signature>> {
         // Sign and send transaction.
-        println!("Generating challenge...");
-        let ix = ore_api::instruction::open(signer.pubkey(), signer.pubkey(), fee_payer.pubkey());
-        self.send_and_confirm(&[ix], ComputeBudget::Dynamic, false)
-            .await
-            .ok();
+        tracing::info!("Generating challenge...");
+        let fee_payer = self.fee_payer();
+        let ix = ore_api::instruction::open(authority.pubkey(), miner, fee_payer.pubkey());
+        self.send_and_confirm_with_signers(
+            &[ix],
+            &[authority.clone()],
+            ComputeBudget::Dynamic,
+            false,
+        )
+        .await
     }
 }
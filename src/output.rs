@@ -0,0 +1,113 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::Miner;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable output
+    #[default]
+    Text,
+    /// Aligned columns
+    Table,
+    /// Machine-readable JSON
+    Json,
+}
+
+/// A serializable command result that can be rendered in every output format.
+pub trait CommandOutput: Serialize {
+    /// Renders the result as free-form text.
+    fn to_text(&self) -> String;
+
+    /// Returns the column headers and rows of the table rendering.
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>);
+}
+
+impl Miner {
+    pub fn print_output<T: CommandOutput>(&self, output: &T) {
+        match self.output {
+            OutputFormat::Text => println!("{}", output.to_text()),
+            OutputFormat::Table => {
+                let (headers, rows) = output.to_table();
+                println!("{}", render_table(&headers, &rows));
+            }
+            OutputFormat::Json => match serde_json::to_string_pretty(output) {
+                Ok(json) => println!("{}", json),
+                Err(err) => eprintln!("error: Failed to serialize output: {}", err),
+            },
+        }
+    }
+}
+
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    // Measure column widths
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(cell.len());
+            }
+        }
+    }
+
+    // Render header, separator and rows
+    let render_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut lines = vec![render_row(headers.to_vec())];
+    lines.push(
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("  "),
+    );
+    for row in rows {
+        lines.push(render_row(row.iter().map(|s| s.as_str()).collect()));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_table;
+
+    #[test]
+    fn render_table_aligns_columns() {
+        let rows = vec![
+            vec!["1".to_string(), "0.5".to_string()],
+            vec!["10".to_string(), "12.25".to_string()],
+        ];
+        assert_eq!(
+            render_table(&["Bus", "Rewards"], &rows),
+            "Bus  Rewards\n---  -------\n1    0.5\n10   12.25"
+        );
+    }
+
+    #[test]
+    fn render_table_widens_to_longest_cell() {
+        let rows = vec![vec!["a".to_string(), "longer value".to_string()]];
+        assert_eq!(
+            render_table(&["Field", "Value"], &rows),
+            "Field  Value\n-----  ------------\na      longer value"
+        );
+    }
+
+    #[test]
+    fn render_table_ignores_extra_cells() {
+        let rows = vec![vec!["1".to_string(), "extra".to_string()]];
+        assert_eq!(render_table(&["Id"], &rows), "Id\n--\n1");
+    }
+
+    #[test]
+    fn render_table_without_rows() {
+        assert_eq!(render_table(&["Id", "Name"], &[]), "Id  Name\n--  ----");
+    }
+}
@@ -1,8 +1,14 @@
-use std::sync::{Arc, Mutex};
+use std::{collections::VecDeque, sync::{Arc, Mutex}, time::Instant};
+use tokio::sync::Notify;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use solana_sdk::signer::Signer;
+
+use crate::{
+    metrics::metrics,
+    status::status,
+    webhooks::{notify, WebhookEvent},
+};
 
 #[derive(Debug, Serialize)]
 struct HashSubmission {
@@ -38,7 +44,28 @@ struct ValidationResult {
 pub struct PoolClient {
     ws_url: String,
     miner: Arc<crate::Miner>,
-    validation_request: Arc<Mutex<Option<ValidationRequest>>>,
+    validation_requests: Arc<Mutex<VecDeque<ValidationRequest>>>,
+    close_notify: Arc<Notify>,
+    /// Send times of hashes the pool has not confirmed or rejected yet.
+    pending_submissions: Arc<Mutex<VecDeque<Instant>>>,
+}
+
+/// Reports the pool as connected until dropped, however `connect` returns.
+struct ConnectedGuard;
+
+impl ConnectedGuard {
+    fn new() -> Self {
+        metrics().pool_connected.set(1);
+        status().set_pool_connected(true);
+        Self
+    }
+}
+
+impl Drop for ConnectedGuard {
+    fn drop(&mut self) {
+        metrics().pool_connected.set(0);
+        status().set_pool_connected(false);
+    }
 }
 
 impl PoolClient {
@@ -46,7 +73,9 @@ impl PoolClient {
         Self {
             ws_url,
             miner,
-            validation_request: Arc::new(Mutex::new(None)),
+            validation_requests: Arc::new(Mutex::new(VecDeque::new())),
+            close_notify: Arc::new(Notify::new()),
+            pending_submissions: Arc::new(Mutex::new(VecDeque::new())),
         }
     }
 
@@ -60,25 +89,48 @@ impl PoolClient {
             "address": self.miner.signer().pubkey().to_string()
         });
         write.send(Message::Text(register_msg.to_string())).await?;
+        let _connected = ConnectedGuard::new();
 
-        // Handle incoming messages
-        while let Some(msg) = read.next().await {
+        // Handle incoming messages until the pool or the miner closes the connection
+        loop {
+            let msg = tokio::select! {
+                msg = read.next() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+                _ = self.close_notify.notified() => {
+                    let _ = write.send(Message::Close(None)).await;
+                    break;
+                }
+            };
             match msg {
                 Ok(Message::Text(text)) => {
                     let data: serde_json::Value = serde_json::from_str(&text)?;
                     match data["type"].as_str() {
                         Some("validate_hash") => {
                             if let Ok(req) = serde_json::from_value::<ValidationRequest>(data) {
-                                *self.validation_request.lock().unwrap() = Some(req);
+                                let mut requests = self.validation_requests.lock().unwrap();
+                                requests.push_back(req);
+                                metrics().validation_queue_depth.set(requests.len() as i64);
                             }
                         },
                         Some("hash_confirmed") => {
-                            println!("Hash confirmed with signature: {}", 
-                                data["signature"].as_str().unwrap_or_default());
+                            let signature = data["signature"].as_str().unwrap_or_default();
+                            tracing::info!("Hash confirmed with signature: {}", signature);
+                            if !signature.is_empty() {
+                                status().add_signature(signature.to_string());
+                            }
+                            metrics().submissions.with_label_values(&["success"]).inc();
+                            if let Some(sent_at) = self.pending_submissions.lock().unwrap().pop_front() {
+                                metrics().landing_latency.observe(sent_at.elapsed().as_secs_f64());
+                            }
                         },
                         Some("hash_rejected") => {
-                            println!("Hash rejected: {}", 
-                                data["error"].as_str().unwrap_or_default());
+                            metrics().submissions.with_label_values(&["failure"]).inc();
+                            self.pending_submissions.lock().unwrap().pop_front();
+                            let error = data["error"].as_str().unwrap_or_default().to_string();
+                            tracing::warn!("Hash rejected: {}", error);
+                            notify(WebhookEvent::HashRejected { error });
                         },
                         _ => {}
                     }
@@ -90,14 +142,23 @@ impl PoolClient {
         Ok(())
     }
 
-    pub async fn submit_hash(&self, hash: String, difficulty: u32, nonce: String) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn submit_hash(&self, hash: String, difficulty: u32, nonce: String, miner_address: String) -> Result<(), Box<dyn std::error::Error>> {
+        let result = self.send_hash(hash, difficulty, nonce, miner_address).await;
+        match result {
+            Ok(()) => self.pending_submissions.lock().unwrap().push_back(Instant::now()),
+            Err(_) => metrics().submissions.with_label_values(&["failure"]).inc(),
+        }
+        result
+    }
+
+    async fn send_hash(&self, hash: String, difficulty: u32, nonce: String, miner_address: String) -> Result<(), Box<dyn std::error::Error>> {
         let (mut ws_stream, _) = connect_async(&self.ws_url).await?;
         
         let submission = HashSubmission {
             msg_type: "submit_hash".to_string(),
             hash,
             difficulty,
-            minerAddress: self.miner.signer().pubkey().to_string(),
+            minerAddress: miner_address,
             nonce,
         };
 
@@ -105,8 +166,17 @@ impl PoolClient {
         Ok(())
     }
 
+    /// Closes the pool connection opened by `connect`.
+    pub fn close(&self) {
+        self.close_notify.notify_one();
+    }
+
+    /// Takes the oldest pending validation request.
     pub async fn get_validation_request(&self) -> Option<ValidationRequest> {
-        self.validation_request.lock().unwrap().clone()
+        let mut requests = self.validation_requests.lock().unwrap();
+        let request = requests.pop_front();
+        metrics().validation_queue_depth.set(requests.len() as i64);
+        request
     }
 
     pub async fn submit_validation_result(
@@ -0,0 +1,73 @@
+use colored::*;
+use indicatif::ProgressBar;
+use solana_rpc_client::spinner;
+
+use crate::logging::spinners_enabled;
+
+/// A spinner on interactive terminals that falls back to log events otherwise, so output
+/// stays readable under systemd and in log files.
+pub struct Progress {
+    bar: Option<ProgressBar>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self {
+            bar: spinners_enabled().then(spinner::new_progress_bar),
+        }
+    }
+
+    /// Updates the spinner. Logged at debug level without a spinner.
+    pub fn set_message(&self, message: String) {
+        match &self.bar {
+            Some(bar) => bar.set_message(message),
+            None => tracing::debug!("{}", message),
+        }
+    }
+
+    /// Shows a recoverable error on the spinner. Logged as a warning without a spinner.
+    pub fn set_error(&self, message: String) {
+        match &self.bar {
+            Some(bar) => bar.set_message(format!("{}: {}", "ERROR".bold().red(), message)),
+            None => tracing::warn!("{}", message),
+        }
+    }
+
+    /// Prints a line above the spinner.
+    pub fn println(&self, message: String) {
+        match &self.bar {
+            Some(bar) => bar.println(message),
+            None => tracing::info!("{}", message),
+        }
+    }
+
+    /// Stops the spinner with a final message.
+    pub fn finish_with_message(&self, message: String) {
+        match &self.bar {
+            Some(bar) => bar.finish_with_message(message),
+            None => tracing::info!("{}", message),
+        }
+    }
+
+    /// Stops the spinner with a success message.
+    pub fn finish_with_success(&self, message: String) {
+        match &self.bar {
+            Some(bar) => bar.finish_with_message(format!("{} {}", "OK".bold().green(), message)),
+            None => tracing::info!("{}", message),
+        }
+    }
+
+    /// Stops the spinner with an error message.
+    pub fn finish_with_error(&self, message: String) {
+        match &self.bar {
+            Some(bar) => bar.finish_with_message(format!("{}: {}", "ERROR".bold().red(), message)),
+            None => tracing::error!("{}", message),
+        }
+    }
+}
@@ -1,42 +1,88 @@
 use std::str::FromStr;
 
 use ore_api::consts::TOKEN_DECIMALS;
+use serde::Serialize;
 use solana_program::pubkey::Pubkey;
-use solana_sdk::signature::Signer;
 use spl_token::amount_to_ui_amount;
 
 use crate::{
     args::ProofArgs,
+    output::CommandOutput,
     utils::{get_proof, proof_pubkey},
     Miner,
 };
 
+#[derive(Debug, Serialize)]
+pub struct ProofOutput {
+    pub address: String,
+    pub authority: String,
+    pub balance: f64,
+    pub last_hash: String,
+    pub last_hash_at: i64,
+    pub last_stake_at: i64,
+    pub miner: String,
+    pub total_hashes: u64,
+    pub total_rewards: f64,
+}
+
+impl CommandOutput for ProofOutput {
+    fn to_text(&self) -> String {
+        [
+            format!("Address: {}", self.address),
+            format!("Authority: {}", self.authority),
+            format!("Balance: {:?} ORE", self.balance),
+            format!("Last hash: {}", self.last_hash),
+            format!("Last hash at: {:?}", self.last_hash_at),
+            format!("Last stake at: {:?}", self.last_stake_at),
+            format!("Miner: {}", self.miner),
+            format!("Total hashes: {:?}", self.total_hashes),
+            format!("Total rewards: {:?} ORE", self.total_rewards),
+        ]
+        .join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        (
+            vec!["Field", "Value"],
+            vec![
+                vec!["Address".into(), self.address.clone()],
+                vec!["Authority".into(), self.authority.clone()],
+                vec!["Balance (ORE)".into(), self.balance.to_string()],
+                vec!["Last hash".into(), self.last_hash.clone()],
+                vec!["Last hash at".into(), self.last_hash_at.to_string()],
+                vec!["Last stake at".into(), self.last_stake_at.to_string()],
+                vec!["Miner".into(), self.miner.clone()],
+                vec!["Total hashes".into(), self.total_hashes.to_string()],
+                vec!["Total rewards (ORE)".into(), self.total_rewards.to_string()],
+            ],
+        )
+    }
+}
+
 impl Miner {
     pub async fn proof(&self, args: ProofArgs) {
         let signer = self.signer();
         let address = if let Some(address) = args.address {
-            Pubkey::from_str(&address).unwrap()
+            if let Ok(address) = Pubkey::from_str(&address) {
+                address
+            } else {
+                println!("Invalid address: {:?}", address);
+                return;
+            }
         } else {
             proof_pubkey(signer.pubkey())
         };
         let proof = get_proof(&self.rpc_client, address).await;
-        println!("Address: {:?}", address);
-        println!("Authority: {:?}", proof.authority);
-        println!(
-            "Balance: {:?} ORE",
-            amount_to_ui_amount(proof.balance, TOKEN_DECIMALS)
-        );
-        println!(
-            "Last hash: {}",
-            solana_sdk::hash::Hash::new_from_array(proof.last_hash).to_string()
-        );
-        println!("Last hash at: {:?}", proof.last_hash_at);
-        println!("Last stake at: {:?}", proof.last_stake_at);
-        println!("Miner: {:?}", proof.miner);
-        println!("Total hashes: {:?}", proof.total_hashes);
-        println!(
-            "Total rewards: {:?} ORE",
-            amount_to_ui_amount(proof.total_rewards, TOKEN_DECIMALS)
-        );
+        self.print_output(&ProofOutput {
+            address: address.to_string(),
+            authority: proof.authority.to_string(),
+            balance: amount_to_ui_amount(proof.balance, TOKEN_DECIMALS),
+            last_hash: solana_sdk::hash::Hash::new_from_array(proof.last_hash).to_string(),
+            last_hash_at: proof.last_hash_at,
+            last_stake_at: proof.last_stake_at,
+            miner: proof.miner.to_string(),
+            total_hashes: proof.total_hashes,
+            total_rewards: amount_to_ui_amount(proof.total_rewards, TOKEN_DECIMALS),
+        });
     }
 }
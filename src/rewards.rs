@@ -1,28 +1,114 @@
+use std::str::FromStr;
+
+use ore_api::consts::TOKEN_DECIMALS;
+use serde::Serialize;
+use solana_program::pubkey::Pubkey;
+use spl_token::amount_to_ui_amount;
+
 use crate::{
-    utils::{amount_u64_to_string, get_config},
+    args::RewardsArgs,
+    output::CommandOutput,
+    utils::{get_config, get_proof_with_authority, stake_multiplier},
     Miner,
 };
 
+#[derive(Debug, Serialize)]
+pub struct RewardRate {
+    pub difficulty: u64,
+    pub reward: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boosted_reward: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RewardsOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<f64>,
+    pub rates: Vec<RewardRate>,
+}
+
+impl CommandOutput for RewardsOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![];
+        if let (Some(address), Some(multiplier)) = (&self.address, self.multiplier) {
+            lines.push(format!(
+                "Stake multiplier for {}: {:.4}x",
+                address, multiplier
+            ));
+        }
+        for r in self.rates.iter() {
+            match r.boosted_reward {
+                Some(boosted) => lines.push(format!(
+                    "{}: {} ORE ({} ORE with stake)",
+                    r.difficulty, r.reward, boosted
+                )),
+                None => lines.push(format!("{}: {} ORE", r.difficulty, r.reward)),
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        if self.multiplier.is_some() {
+            return (
+                vec!["Difficulty", "Reward (ORE)", "With stake (ORE)"],
+                self.rates
+                    .iter()
+                    .map(|r| {
+                        vec![
+                            r.difficulty.to_string(),
+                            r.reward.to_string(),
+                            r.boosted_reward
+                                .map(|boosted| boosted.to_string())
+                                .unwrap_or_default(),
+                        ]
+                    })
+                    .collect(),
+            );
+        }
+        (
+            vec!["Difficulty", "Reward (ORE)"],
+            self.rates
+                .iter()
+                .map(|r| vec![r.difficulty.to_string(), r.reward.to_string()])
+                .collect(),
+        )
+    }
+}
+
 impl Miner {
-    pub async fn rewards(&self) {
+    pub async fn rewards(&self, args: RewardsArgs) {
         let config = get_config(&self.rpc_client).await;
         let base_reward_rate = config.base_reward_rate;
 
-        let mut s = format!(
-            "{}: {} ORE",
-            config.min_difficulty,
-            amount_u64_to_string(base_reward_rate)
-        )
-        .to_string();
-        for i in 1..32 {
+        // Get the stake multiplier of the proof, if requested
+        let multiplier = if let Some(address) = &args.address {
+            let Ok(address) = Pubkey::from_str(address) else {
+                println!("Invalid address: {:?}", address);
+                return;
+            };
+            let proof = get_proof_with_authority(&self.rpc_client, address).await;
+            Some(stake_multiplier(proof.balance, config.top_balance))
+        } else {
+            None
+        };
+
+        let mut rates = vec![];
+        for i in 0..32 {
             let reward_rate = base_reward_rate.saturating_mul(2u64.saturating_pow(i));
-            s = format!(
-                "{}\n{}: {} ORE",
-                s,
-                config.min_difficulty as u32 + i,
-                amount_u64_to_string(reward_rate)
-            );
+            rates.push(RewardRate {
+                difficulty: config.min_difficulty as u64 + i as u64,
+                reward: amount_to_ui_amount(reward_rate, TOKEN_DECIMALS),
+                boosted_reward: multiplier
+                    .map(|m| amount_to_ui_amount((reward_rate as f64 * m) as u64, TOKEN_DECIMALS)),
+            });
         }
-        println!("{}", s);
+        self.print_output(&RewardsOutput {
+            address: args.address,
+            multiplier,
+            rates,
+        });
     }
 }
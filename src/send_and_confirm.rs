@@ -1,6 +1,11 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
 
-use colored::*;
 use solana_client::{
     client_error::{ClientError, ClientErrorKind, Result as ClientResult},
     rpc_config::RpcSendTransactionConfig,
@@ -9,18 +14,32 @@ use solana_program::{
     instruction::Instruction,
     native_token::{lamports_to_sol, sol_to_lamports},
 };
-use solana_rpc_client::spinner;
 use solana_sdk::{
     commitment_config::CommitmentLevel,
     compute_budget::ComputeBudgetInstruction,
+    packet::PACKET_DATA_SIZE,
     signature::{Signature, Signer},
     transaction::Transaction,
 };
 use solana_transaction_status::{TransactionConfirmationStatus, UiTransactionEncoding};
 
-use crate::Miner;
+use crate::{
+    metrics::metrics,
+    progress::Progress,
+    signer::MinerSigner,
+    status::status,
+    webhooks::{notify, record_transaction_result, WebhookEvent},
+    Miner,
+};
+
+/// Lamports charged per signature.
+pub const BASE_FEE: u64 = 5000;
 
-const MIN_SOL_BALANCE: f64 = 0.005;
+/// The last reported `BalanceLevel`, so each change is reported once.
+static LAST_BALANCE_LEVEL: AtomicU8 = AtomicU8::new(BalanceLevel::Ok as u8);
+
+/// The compute unit price of the last landed transaction, so balance estimates follow dynamic fees.
+static LAST_PRIORITY_FEE: Mutex<Option<u64>> = Mutex::new(None);
 
 const RPC_RETRIES: usize = 0;
 const _SIMULATION_RETRIES: usize = 4;
@@ -35,31 +54,71 @@ pub enum ComputeBudget {
     Fixed(u32),
 }
 
+/// Fee payer balance relative to `--warn-balance` and `--min-balance`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceLevel {
+    Ok,
+    Low,
+    Insufficient,
+}
+
+#[derive(Debug)]
+pub struct BalanceCheck {
+    pub balance: u64,
+    pub fee_per_tx: u64,
+    pub transactions_remaining: u64,
+    pub level: BalanceLevel,
+}
+
 impl Miner {
     pub async fn send_and_confirm(
         &self,
         ixs: &[Instruction],
         compute_budget: ComputeBudget,
         skip_confirm: bool,
-    ) -> ClientResult<Signature> {
-        let signer = self.signer();
-        let client = self.rpc_client.clone();
-        let fee_payer = self.fee_payer();
-
-        // Return error, if balance is zero
-        self.check_balance().await;
+    ) -> ClientResult<Option<Signature>> {
+        self.send_and_confirm_with_signers(ixs, &[self.signer()], compute_budget, skip_confirm)
+            .await
+    }
 
+    /// Sends instructions that need signatures from several authorities, such as the
+    /// mine instructions of a fleet of proofs sharing one fee payer. Returns `None` when
+    /// the transaction was only simulated or exported and never sent.
+    pub async fn send_and_confirm_with_signers(
+        &self,
+        ixs: &[Instruction],
+        signers: &[MinerSigner],
+        compute_budget: ComputeBudget,
+        skip_confirm: bool,
+    ) -> ClientResult<Option<Signature>> {
         // Set compute budget
         let mut final_ixs = vec![];
-        match compute_budget {
+        let cu_limit = match compute_budget {
             ComputeBudget::Dynamic => {
                 // TODO simulate
-                final_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(1_400_000))
+                1_400_000
             }
-            ComputeBudget::Fixed(cus) => {
-                final_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(cus))
+            ComputeBudget::Fixed(cus) => cus,
+        };
+
+        // Return error, if balance is below the minimum
+        if !self.dry_run && !self.sign_only {
+            match self.check_balance(cu_limit).await {
+                Ok(check) if check.level == BalanceLevel::Insufficient => {
+                    return Err(ClientError {
+                        request: None,
+                        kind: ClientErrorKind::Custom(format!(
+                            "Insufficient balance: {} SOL. Please top up with at least {} SOL",
+                            lamports_to_sol(check.balance),
+                            self.min_balance
+                        )),
+                    });
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!("Failed to check fee payer balance: {}", err),
             }
         }
+        final_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(cu_limit));
 
         // Set compute unit price
         final_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
@@ -69,7 +128,50 @@ impl Miner {
         // Add in user instructions
         final_ixs.extend_from_slice(ixs);
 
+        // Export a partially signed tx instead of sending
+        if self.sign_only {
+            return self.sign_only(&final_ixs, signers).await.map(|_| None);
+        }
+
         // Build tx
+        let fee_payer = self.fee_payer();
+        let tx = Transaction::new_with_payer(&final_ixs, Some(&fee_payer.pubkey()));
+
+        // Simulate and exit without sending
+        if self.dry_run {
+            let fee = if self.dynamic_fee {
+                self.dynamic_fee()
+                    .await
+                    .unwrap_or(self.priority_fee.unwrap_or(0))
+            } else {
+                self.priority_fee.unwrap_or(0)
+            };
+            final_ixs[1] = ComputeBudgetInstruction::set_compute_unit_price(fee);
+            let tx = Transaction::new_with_payer(&final_ixs, Some(&fee_payer.pubkey()));
+            return self.dry_run(&tx, cu_limit, fee).await.map(|_| None);
+        }
+
+        self.send_and_confirm_tx(tx, Some(final_ixs), signers, skip_confirm)
+            .await
+            .map(Some)
+    }
+
+    /// Submits a transaction and waits for confirmation. If `final_ixs` is provided, the
+    /// transaction is rebuilt and re-signed by `signers` and the fee payer with a fresh
+    /// blockhash as it expires. Otherwise the transaction is sent exactly as signed.
+    #[tracing::instrument(
+        name = "transaction",
+        skip_all,
+        fields(signers = signers.len(), signature = tracing::field::Empty)
+    )]
+    pub async fn send_and_confirm_tx(
+        &self,
+        mut tx: Transaction,
+        mut final_ixs: Option<Vec<Instruction>>,
+        signers: &[MinerSigner],
+        skip_confirm: bool,
+    ) -> ClientResult<Signature> {
+        let client = self.rpc_client.clone();
         let send_cfg = RpcSendTransactionConfig {
             skip_preflight: true,
             preflight_commitment: Some(CommitmentLevel::Confirmed),
@@ -77,16 +179,19 @@ impl Miner {
             max_retries: Some(RPC_RETRIES),
             min_context_slot: None,
         };
-        let mut tx = Transaction::new_with_payer(&final_ixs, Some(&fee_payer.pubkey()));
 
         // Submit tx
-        let progress_bar = spinner::new_progress_bar();
+        let progress_bar = Progress::new();
+        let started_at = Instant::now();
+        let mut cu_price = self.priority_fee.unwrap_or(0);
         let mut attempts = 0;
         loop {
-            progress_bar.set_message(format!("Submitting transaction... (attempt {})", attempts,));
+            progress_bar.set_message(format!("Submitting transaction... (attempt {})", attempts));
 
             // Sign tx with a new blockhash (after approximately ~45 sec)
-            if attempts % 10 == 0 {
+            if let Some(final_ixs) = final_ixs.as_mut().filter(|_| attempts % 10 == 0) {
+                let fee_payer = self.fee_payer();
+
                 // Reset the compute unit price
                 if self.dynamic_fee {
                     let fee = if let Some(fee) = self.dynamic_fee().await {
@@ -94,11 +199,16 @@ impl Miner {
                         fee
                     } else {
                         let fee = self.priority_fee.unwrap_or(0);
-                        progress_bar.println(format!("  {} Dynamic fees not supported by this RPC. Falling back to static value: {} microlamports", "WARNING".bold().yellow(), fee));
+                        tracing::warn!(
+                            "Dynamic fees not supported by this RPC. Falling back to static value: {} microlamports",
+                            fee
+                        );
                         fee
                     };
+                    cu_price = fee;
                     final_ixs.remove(1);
                     final_ixs.insert(1, ComputeBudgetInstruction::set_compute_unit_price(fee));
+                    tx = Transaction::new_with_payer(final_ixs, Some(&fee_payer.pubkey()));
                 }
 
                 // Resign the tx
@@ -106,16 +216,20 @@ impl Miner {
                     .get_latest_blockhash_with_commitment(self.rpc_client.commitment())
                     .await
                     .unwrap();
-                if signer.pubkey() == fee_payer.pubkey() {
-                    tx.sign(&[&signer], hash);
-                } else {
-                    tx.sign(&[&signer, &fee_payer], hash);
+                let mut tx_signers: Vec<&dyn Signer> = vec![&*fee_payer];
+                for signer in signers.iter() {
+                    if !tx_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+                        tx_signers.push(&**signer);
+                    }
                 }
+                tx.sign(&tx_signers, hash);
             }
 
             // Send transaction
             match client.send_transaction_with_config(&tx, send_cfg).await {
                 Ok(sig) => {
+                    tracing::Span::current().record("signature", tracing::field::display(sig));
+
                     // Skip confirmation
                     if skip_confirm {
                         progress_bar.finish_with_message(format!("Sent: {}", sig));
@@ -127,29 +241,40 @@ impl Miner {
                         std::thread::sleep(Duration::from_millis(CONFIRM_DELAY));
                         match client.get_signature_statuses(&[sig]).await {
                             Ok(signature_statuses) => {
-                                for status in signature_statuses.value {
-                                    if let Some(status) = status {
-                                        if let Some(err) = status.err {
-                                            progress_bar.finish_with_message(format!(
-                                                "{}: {}",
-                                                "ERROR".bold().red(),
-                                                err
-                                            ));
+                                for tx_status in signature_statuses.value {
+                                    if let Some(tx_status) = tx_status {
+                                        if let Some(err) = tx_status.err {
+                                            metrics()
+                                                .submissions
+                                                .with_label_values(&["failure"])
+                                                .inc();
+                                            record_transaction_result(Some(&err.to_string()));
+                                            progress_bar.finish_with_error(err.to_string());
                                             return Err(ClientError {
                                                 request: None,
                                                 kind: ClientErrorKind::Custom(err.to_string()),
                                             });
                                         }
-                                        if let Some(confirmation) = status.confirmation_status {
+                                        if let Some(confirmation) = tx_status.confirmation_status {
                                             match confirmation {
                                                 TransactionConfirmationStatus::Processed => {}
                                                 TransactionConfirmationStatus::Confirmed
                                                 | TransactionConfirmationStatus::Finalized => {
-                                                    progress_bar.finish_with_message(format!(
-                                                        "{} {}",
-                                                        "OK".bold().green(),
-                                                        sig
-                                                    ));
+                                                    let metrics = metrics();
+                                                    metrics
+                                                        .submissions
+                                                        .with_label_values(&["success"])
+                                                        .inc();
+                                                    metrics.landing_latency.observe(
+                                                        started_at.elapsed().as_secs_f64(),
+                                                    );
+                                                    metrics.priority_fee.set(cu_price as i64);
+                                                    *LAST_PRIORITY_FEE.lock().unwrap() =
+                                                        Some(cu_price);
+                                                    status().add_signature(sig.to_string());
+                                                    record_transaction_result(None);
+                                                    progress_bar
+                                                        .finish_with_success(sig.to_string());
                                                     return Ok(sig);
                                                 }
                                             }
@@ -160,11 +285,7 @@ impl Miner {
 
                             // Handle confirmation errors
                             Err(err) => {
-                                progress_bar.set_message(format!(
-                                    "{}: {}",
-                                    "ERROR".bold().red(),
-                                    err.kind().to_string()
-                                ));
+                                progress_bar.set_error(err.kind().to_string());
                             }
                         }
                     }
@@ -172,11 +293,7 @@ impl Miner {
 
                 // Handle submit errors
                 Err(err) => {
-                    progress_bar.set_message(format!(
-                        "{}: {}",
-                        "ERROR".bold().red(),
-                        err.kind().to_string()
-                    ));
+                    progress_bar.set_error(err.kind().to_string());
                 }
             }
 
@@ -184,7 +301,9 @@ impl Miner {
             std::thread::sleep(Duration::from_millis(GATEWAY_DELAY));
             attempts += 1;
             if attempts > GATEWAY_RETRIES {
-                progress_bar.finish_with_message(format!("{}: Max retries", "ERROR".bold().red()));
+                metrics().submissions.with_label_values(&["failure"]).inc();
+                record_transaction_result(Some("Max retries"));
+                progress_bar.finish_with_error("Max retries".to_string());
                 return Err(ClientError {
                     request: None,
                     kind: ClientErrorKind::Custom("Max retries".into()),
@@ -193,22 +312,98 @@ impl Miner {
         }
     }
 
-    pub async fn check_balance(&self) {
-        // Throw error if balance is less than min
-        if let Ok(balance) = self
-            .rpc_client
-            .get_balance(&self.fee_payer().pubkey())
-            .await
-        {
-            if balance <= sol_to_lamports(MIN_SOL_BALANCE) {
-                panic!(
-                    "{} Insufficient balance: {} SOL\nPlease top up with at least {} SOL",
-                    "ERROR".bold().red(),
-                    lamports_to_sol(balance),
-                    MIN_SOL_BALANCE
-                );
+    /// Packs groups of instructions, each with the authority that must sign it, into
+    /// as few transactions as the packet size limit allows. Groups are never split.
+    pub fn pack_instructions(
+        &self,
+        groups: Vec<(MinerSigner, Vec<Instruction>)>,
+    ) -> Vec<(Vec<Instruction>, Vec<MinerSigner>)> {
+        let fee_payer = self.fee_payer().pubkey();
+        let fits = |ixs: &[Instruction]| {
+            let mut final_ixs = vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(0),
+                ComputeBudgetInstruction::set_compute_unit_price(0),
+            ];
+            final_ixs.extend_from_slice(ixs);
+            let tx = Transaction::new_with_payer(&final_ixs, Some(&fee_payer));
+            bincode::serialized_size(&tx)
+                .map(|size| size as usize <= PACKET_DATA_SIZE)
+                .unwrap_or(false)
+        };
+
+        let mut batches: Vec<(Vec<Instruction>, Vec<MinerSigner>)> = vec![];
+        for (signer, ixs) in groups {
+            if let Some((batch_ixs, batch_signers)) = batches.last_mut() {
+                let candidate = [batch_ixs.as_slice(), ixs.as_slice()].concat();
+                if fits(&candidate) {
+                    *batch_ixs = candidate;
+                    batch_signers.push(signer);
+                    continue;
+                }
+            }
+            batches.push((ixs, vec![signer]));
+        }
+        batches
+    }
+
+    /// Fetches the fee payer balance and compares it with the configured thresholds,
+    /// estimating how many transactions with `cu_limit` compute units it still covers.
+    /// Warnings and webhooks fire only when the level changes.
+    pub async fn check_balance(&self, cu_limit: u32) -> ClientResult<BalanceCheck> {
+        let fee_payer = self.fee_payer().pubkey();
+        let balance = self.rpc_client.get_balance(&fee_payer).await?;
+        let cu_price = LAST_PRIORITY_FEE
+            .lock()
+            .unwrap()
+            .unwrap_or(self.priority_fee.unwrap_or(0));
+        let fee_per_tx = BASE_FEE + (cu_limit as u64).saturating_mul(cu_price) / 1_000_000;
+        let min_balance = sol_to_lamports(self.min_balance);
+        let transactions_remaining = balance.saturating_sub(min_balance) / fee_per_tx.max(1);
+        let level = if balance <= min_balance {
+            BalanceLevel::Insufficient
+        } else if balance <= sol_to_lamports(self.warn_balance) {
+            BalanceLevel::Low
+        } else {
+            BalanceLevel::Ok
+        };
+
+        // Report changes in level
+        if LAST_BALANCE_LEVEL.swap(level as u8, Ordering::Relaxed) != level as u8 {
+            match level {
+                BalanceLevel::Ok => {}
+                BalanceLevel::Low => {
+                    tracing::warn!(
+                        "Low balance: {} SOL covers about {} more transactions",
+                        lamports_to_sol(balance),
+                        transactions_remaining
+                    );
+                    notify(WebhookEvent::LowBalance {
+                        address: fee_payer.to_string(),
+                        balance: lamports_to_sol(balance),
+                        threshold: self.warn_balance,
+                    });
+                }
+                BalanceLevel::Insufficient => {
+                    tracing::error!(
+                        "Insufficient balance: {} SOL. Please top up with at least {} SOL",
+                        lamports_to_sol(balance),
+                        self.min_balance
+                    );
+                    notify(WebhookEvent::LowBalance {
+                        address: fee_payer.to_string(),
+                        balance: lamports_to_sol(balance),
+                        threshold: self.min_balance,
+                    });
+                }
             }
         }
+
+        Ok(BalanceCheck {
+            balance,
+            fee_per_tx,
+            transactions_remaining,
+            level,
+        })
     }
 
     // TODO
@@ -268,3 +463,102 @@ impl Miner {
         // }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_program::instruction::AccountMeta;
+    use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+
+    use super::*;
+    use crate::output::OutputFormat;
+
+    fn miner() -> Miner {
+        let miner = Miner::new(
+            Arc::new(RpcClient::new("http://localhost:8899".to_string())),
+            None,
+            None,
+            None,
+            false,
+            None,
+            OutputFormat::Text,
+            false,
+            false,
+            false,
+            None,
+            0.0,
+            0.0,
+        );
+        let _ = miner.signer.set(Arc::new(Keypair::new()));
+        miner
+    }
+
+    fn group(data_len: usize) -> (MinerSigner, Vec<Instruction>) {
+        let signer: MinerSigner = Arc::new(Keypair::new());
+        let ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &vec![0; data_len],
+            vec![AccountMeta::new(signer.pubkey(), true)],
+        );
+        (signer, vec![ix])
+    }
+
+    #[test]
+    fn pack_instructions_batches_small_groups_together() {
+        let miner = miner();
+        let groups: Vec<_> = (0..3).map(|_| group(8)).collect();
+        let batches = miner.pack_instructions(groups);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].0.len(), 3);
+        assert_eq!(batches[0].1.len(), 3);
+    }
+
+    #[test]
+    fn pack_instructions_splits_at_packet_size() {
+        let miner = miner();
+        let groups: Vec<_> = (0..20).map(|_| group(100)).collect();
+        let order: Vec<Pubkey> = groups.iter().map(|(signer, _)| signer.pubkey()).collect();
+        let batches = miner.pack_instructions(groups);
+        assert!(batches.len() > 1);
+
+        // Every batch fits in a packet with the compute budget instructions
+        let fee_payer = miner.fee_payer().pubkey();
+        for (ixs, signers) in batches.iter() {
+            assert_eq!(ixs.len(), signers.len());
+            let mut final_ixs = vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(0),
+                ComputeBudgetInstruction::set_compute_unit_price(0),
+            ];
+            final_ixs.extend_from_slice(ixs);
+            let tx = Transaction::new_with_payer(&final_ixs, Some(&fee_payer));
+            assert!(bincode::serialized_size(&tx).unwrap() as usize <= PACKET_DATA_SIZE);
+        }
+
+        // Groups keep their order across batches
+        let packed: Vec<Pubkey> = batches
+            .iter()
+            .flat_map(|(_, signers)| signers.iter().map(|signer| signer.pubkey()))
+            .collect();
+        assert_eq!(packed, order);
+    }
+
+    #[test]
+    fn pack_instructions_never_splits_a_group() {
+        let miner = miner();
+        let (signer, mut ixs) = group(8);
+        ixs.extend(group(8).1);
+        let batches = miner.pack_instructions(vec![(signer, ixs), group(8)]);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].0.len(), 3);
+        assert_eq!(batches[0].1.len(), 2);
+    }
+
+    #[test]
+    fn pack_instructions_keeps_oversized_group_alone() {
+        let miner = miner();
+        let batches = miner.pack_instructions(vec![group(8), group(PACKET_DATA_SIZE), group(8)]);
+        assert_eq!(batches.len(), 3);
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::tui::restore_terminal;
+
+/// Installs SIGINT and SIGTERM handlers and returns the flag they set.
+///
+/// The first signal sets the flag so the miner can stop hashing and finish any in-flight
+/// submission. A second signal exits immediately.
+pub fn install_shutdown_handler() -> Arc<AtomicBool> {
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let flag = stop_flag.clone();
+    tokio::spawn(async move {
+        loop {
+            wait_for_signal().await;
+            if flag.swap(true, Ordering::Relaxed) {
+                tracing::warn!("Forcing exit");
+                restore_terminal();
+                std::process::exit(130);
+            }
+            tracing::info!(
+                "Shutting down after the current submission. Press Ctrl-C again to force exit."
+            );
+        }
+    });
+    stop_flag
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,104 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Serialize;
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_program::instruction::Instruction;
+use solana_sdk::{
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+
+use crate::{output::CommandOutput, signer::MinerSigner, Miner};
+
+#[derive(Debug, Serialize)]
+pub struct SignOnlyOutput {
+    pub blockhash: String,
+    pub signers: Vec<String>,
+    pub absent_signers: Vec<String>,
+    pub transaction_base58: String,
+    pub transaction_base64: String,
+}
+
+impl CommandOutput for SignOnlyOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![format!("Blockhash: {}", self.blockhash)];
+        for signer in self.signers.iter() {
+            lines.push(format!("Signer: {}", signer));
+        }
+        for signer in self.absent_signers.iter() {
+            lines.push(format!("Absent signer: {}", signer));
+        }
+        lines.push(format!("Transaction (base58): {}", self.transaction_base58));
+        lines.push(format!("Transaction (base64): {}", self.transaction_base64));
+        lines.join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let mut rows = vec![vec!["Blockhash".into(), self.blockhash.clone()]];
+        for signer in self.signers.iter() {
+            rows.push(vec!["Signer".into(), signer.clone()]);
+        }
+        for signer in self.absent_signers.iter() {
+            rows.push(vec!["Absent signer".into(), signer.clone()]);
+        }
+        rows.push(vec!["Base58".into(), self.transaction_base58.clone()]);
+        rows.push(vec!["Base64".into(), self.transaction_base64.clone()]);
+        (vec!["Field", "Value"], rows)
+    }
+}
+
+impl Miner {
+    /// Returns whether transactions are signed without network access, which takes
+    /// `--sign-only` with a `--blockhash`.
+    pub fn offline(&self) -> bool {
+        self.sign_only && self.blockhash.is_some()
+    }
+
+    /// Signs a transaction with every locally available keypair and prints it for
+    /// `submit-signed` instead of sending it.
+    pub async fn sign_only(
+        &self,
+        final_ixs: &[Instruction],
+        signers: &[MinerSigner],
+    ) -> ClientResult<Signature> {
+        // Keypairs held elsewhere are pubkeys here, and leave their signatures absent
+        let fee_payer = self.fee_payer();
+
+        // Use the provided blockhash so the tx can be signed offline
+        let blockhash = match self.blockhash {
+            Some(blockhash) => blockhash,
+            None => self.rpc_client.get_latest_blockhash().await?,
+        };
+
+        // Partially sign with every local signer at once
+        let mut tx = Transaction::new_with_payer(final_ixs, Some(&fee_payer.pubkey()));
+        let mut local_signers: Vec<&dyn Signer> = vec![];
+        for signer in signers.iter().chain(std::iter::once(&fee_payer)) {
+            if !local_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+                local_signers.push(&**signer);
+            }
+        }
+        tx.partial_sign(&local_signers, blockhash);
+
+        // Print the encoded tx
+        let num_signers = tx.message.header.num_required_signatures as usize;
+        let (signers, absent_signers): (Vec<_>, Vec<_>) = tx.message.account_keys[..num_signers]
+            .iter()
+            .zip(tx.signatures.iter())
+            .partition(|(_, sig)| **sig != Signature::default());
+        let bytes = bincode::serialize(&tx).map_err(|err| ClientError {
+            request: None,
+            kind: ClientErrorKind::Custom(err.to_string()),
+        })?;
+        self.print_output(&SignOnlyOutput {
+            blockhash: blockhash.to_string(),
+            signers: signers.iter().map(|(key, _)| key.to_string()).collect(),
+            absent_signers: absent_signers
+                .iter()
+                .map(|(key, _)| key.to_string())
+                .collect(),
+            transaction_base58: bs58::encode(&bytes).into_string(),
+            transaction_base64: STANDARD.encode(&bytes),
+        });
+        Ok(tx.signatures[0])
+    }
+}
@@ -0,0 +1,53 @@
+use std::{error::Error, sync::Arc};
+
+use solana_sdk::signature::{
+    keypair_from_seed_phrase_and_passphrase, read_keypair, read_keypair_file, Keypair, Signer,
+};
+
+/// A signer shared across the miner and its background tasks.
+pub type MinerSigner = Arc<dyn Signer + Send + Sync>;
+
+/// Loads a signer from a keypair URI.
+///
+/// Supported sources are:
+/// - `<PATH>` or `file://<PATH>`: a JSON keypair file
+/// - `prompt://`: a seed phrase and optional passphrase entered on the terminal
+/// - `stdin` or `stdin://`: a JSON keypair read from stdin
+/// - `env://<VAR>`: a JSON byte array or base58 secret key stored in an environment variable
+pub fn signer_from_uri(
+    uri: &str,
+    keypair_name: &str,
+) -> Result<Box<dyn Signer + Send + Sync>, Box<dyn Error>> {
+    let keypair = if uri == "prompt://" || uri == "prompt:" {
+        let seed_phrase = rpassword::prompt_password(format!("[{}] Seed phrase: ", keypair_name))?;
+        let passphrase = rpassword::prompt_password(format!(
+            "[{}] Passphrase (empty for none): ",
+            keypair_name
+        ))?;
+        keypair_from_seed_phrase_and_passphrase(seed_phrase.trim(), &passphrase)?
+    } else if is_stdin_uri(uri) {
+        read_keypair(&mut std::io::stdin())?
+    } else if let Some(var) = uri.strip_prefix("env://") {
+        let secret =
+            std::env::var(var).map_err(|_| format!("Environment variable {} is not set", var))?;
+        keypair_from_secret(secret.trim())?
+    } else {
+        let path = uri.strip_prefix("file://").unwrap_or(uri);
+        read_keypair_file(path).map_err(|err| format!("No keypair found at {}: {}", path, err))?
+    };
+    Ok(Box::new(keypair))
+}
+
+/// Returns whether the keypair URI reads from stdin, which can only be done once.
+pub fn is_stdin_uri(uri: &str) -> bool {
+    uri == "stdin" || uri == "stdin://" || uri == "-"
+}
+
+fn keypair_from_secret(secret: &str) -> Result<Keypair, Box<dyn Error>> {
+    let bytes: Vec<u8> = if secret.starts_with('[') {
+        serde_json::from_str(secret)?
+    } else {
+        bs58::decode(secret).into_vec()?
+    };
+    Ok(Keypair::from_bytes(&bytes)?)
+}
@@ -1,43 +1,161 @@
 use std::str::FromStr;
 
+use ore_api::consts::TOKEN_DECIMALS;
+use serde::Serialize;
+use solana_client::client_error::Result as ClientResult;
 use solana_program::pubkey::Pubkey;
-use solana_sdk::signature::Signer;
+use solana_sdk::signature::Signature;
+use spl_token::amount_to_ui_amount;
 
 use crate::{
-    args::StakeArgs, cu_limits::CU_LIMIT_CLAIM, send_and_confirm::ComputeBudget,
-    utils::amount_f64_to_u64, Miner,
+    args::StakeArgs,
+    cu_limits::CU_LIMIT_CLAIM,
+    output::CommandOutput,
+    send_and_confirm::ComputeBudget,
+    signer::MinerSigner,
+    utils::{amount_f64_to_u64, get_config, get_proof_with_authority, stake_multiplier},
+    Miner,
 };
 
+#[derive(Debug, Serialize)]
+pub struct StakePlanOutput {
+    pub current_stake: f64,
+    pub additional_stake: f64,
+    pub projected_stake: f64,
+    pub top_stake: f64,
+    pub current_multiplier: f64,
+    pub projected_multiplier: f64,
+    pub reward_uplift_percent: f64,
+}
+
+impl CommandOutput for StakePlanOutput {
+    fn to_text(&self) -> String {
+        [
+            format!("Current stake: {} ORE", self.current_stake),
+            format!("Additional stake: {} ORE", self.additional_stake),
+            format!("Projected stake: {} ORE", self.projected_stake),
+            format!("Top stake: {} ORE", self.top_stake),
+            format!("Current multiplier: {:.4}x", self.current_multiplier),
+            format!("Projected multiplier: {:.4}x", self.projected_multiplier),
+            format!("Reward uplift: {:+.2}%", self.reward_uplift_percent),
+        ]
+        .join("\n")
+    }
+
+    fn to_table(&self) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        (
+            vec!["Field", "Value"],
+            vec![
+                vec!["Current stake (ORE)".into(), self.current_stake.to_string()],
+                vec![
+                    "Additional stake (ORE)".into(),
+                    self.additional_stake.to_string(),
+                ],
+                vec![
+                    "Projected stake (ORE)".into(),
+                    self.projected_stake.to_string(),
+                ],
+                vec!["Top stake (ORE)".into(), self.top_stake.to_string()],
+                vec![
+                    "Current multiplier".into(),
+                    format!("{:.4}", self.current_multiplier),
+                ],
+                vec![
+                    "Projected multiplier".into(),
+                    format!("{:.4}", self.projected_multiplier),
+                ],
+                vec![
+                    "Reward uplift (%)".into(),
+                    format!("{:.2}", self.reward_uplift_percent),
+                ],
+            ],
+        )
+    }
+}
+
 impl Miner {
     pub async fn stake(&self, args: StakeArgs) {
-        // Get signer
+        if args.plan {
+            self.stake_plan(args).await;
+        } else {
+            self.stake_from(&self.signer(), args).await;
+        }
+    }
+
+    /// Stakes tokens into the authority's proof, returning the amount staked and the
+    /// result of sending the transaction, or `None` if nothing was sent.
+    pub async fn stake_from(
+        &self,
+        signer: &MinerSigner,
+        args: StakeArgs,
+    ) -> Option<(u64, ClientResult<Option<Signature>>)> {
+        // Signing offline rules out looking up the token balance
+        if self.offline() && args.amount.is_none() {
+            println!("Signing with --blockhash requires --amount");
+            return None;
+        }
+        let (sender, amount) = self.stake_amount(signer.pubkey(), &args).await?;
+
+        // Send tx
+        let ix = ore_api::instruction::stake(signer.pubkey(), sender, amount);
+        let result = self
+            .send_and_confirm_with_signers(
+                &[ix],
+                &[signer.clone()],
+                ComputeBudget::Fixed(CU_LIMIT_CLAIM),
+                false,
+            )
+            .await;
+        Some((amount, result))
+    }
+
+    /// Projects the stake multiplier and reward uplift of staking more tokens.
+    async fn stake_plan(&self, args: StakeArgs) {
         let signer = self.signer();
-        let sender = match args.sender {
-            Some(sender) => Pubkey::from_str(&sender).expect("Failed to parse sender address"),
+        let amount = match args.amount {
+            Some(amount) => amount_f64_to_u64(amount),
+            None => match self.stake_amount(signer.pubkey(), &args).await {
+                Some((_sender, amount)) => amount,
+                None => return,
+            },
+        };
+        let config = get_config(&self.rpc_client).await;
+        let proof = get_proof_with_authority(&self.rpc_client, signer.pubkey()).await;
+        let projected_balance = proof.balance.saturating_add(amount);
+        let current_multiplier = stake_multiplier(proof.balance, config.top_balance);
+        let projected_multiplier = stake_multiplier(projected_balance, config.top_balance);
+        self.print_output(&StakePlanOutput {
+            current_stake: amount_to_ui_amount(proof.balance, TOKEN_DECIMALS),
+            additional_stake: amount_to_ui_amount(amount, TOKEN_DECIMALS),
+            projected_stake: amount_to_ui_amount(projected_balance, TOKEN_DECIMALS),
+            top_stake: amount_to_ui_amount(config.top_balance, TOKEN_DECIMALS),
+            current_multiplier,
+            projected_multiplier,
+            reward_uplift_percent: (projected_multiplier / current_multiplier - 1.0) * 100.0,
+        });
+    }
+
+    /// Resolves the token account to stake from and the amount to stake.
+    async fn stake_amount(&self, authority: Pubkey, args: &StakeArgs) -> Option<(Pubkey, u64)> {
+        let sender = match &args.sender {
+            Some(sender) => Pubkey::from_str(sender).expect("Failed to parse sender address"),
             None => spl_associated_token_account::get_associated_token_address(
-                &signer.pubkey(),
+                &authority,
                 &ore_api::consts::MINT_ADDRESS,
             ),
         };
 
-        // Get token account
-        let Ok(Some(token_account)) = self.rpc_client.get_token_account(&sender).await else {
-            println!("Failed to fetch token account");
-            return;
-        };
-
-        // Parse amount
+        // Parse amount, fetching the token balance only to stake all of it
         let amount: u64 = if let Some(amount) = args.amount {
             amount_f64_to_u64(amount)
         } else {
+            let Ok(Some(token_account)) = self.rpc_client.get_token_account(&sender).await else {
+                println!("Failed to fetch token account");
+                return None;
+            };
             u64::from_str(token_account.token_amount.amount.as_str())
                 .expect("Failed to parse token balance")
         };
-
-        // Send tx
-        let ix = ore_api::instruction::stake(signer.pubkey(), sender, amount);
-        self.send_and_confirm(&[ix], ComputeBudget::Fixed(CU_LIMIT_CLAIM), false)
-            .await
-            .ok();
+        Some((sender, amount))
     }
 }
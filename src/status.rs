@@ -0,0 +1,279 @@
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use axum::{
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+
+/// Number of recent transaction signatures kept for the status API.
+const RECENT_SIGNATURES: usize = 20;
+
+/// Number of recent log lines kept for the dashboard.
+const RECENT_LOG_LINES: usize = 200;
+
+/// Number of difficulty buckets in the session histogram.
+const DIFFICULTY_BUCKETS: usize = 64;
+
+/// A challenge being hashed in the current round.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChallengeStatus {
+    pub authority: String,
+    pub challenge: String,
+    pub time_limit: u64,
+}
+
+/// Totals for the current `mine` session.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub started_at: i64,
+    pub rounds: u64,
+    pub solutions: u64,
+    pub submitted: u64,
+    pub best_difficulty: u32,
+}
+
+impl SessionSummary {
+    pub fn new() -> Self {
+        Self {
+            started_at: chrono::Utc::now().timestamp(),
+            rounds: 0,
+            solutions: 0,
+            submitted: 0,
+            best_difficulty: 0,
+        }
+    }
+
+    pub fn print(&self) {
+        tracing::info!(
+            duration_secs = chrono::Utc::now().timestamp() - self.started_at,
+            rounds = self.rounds,
+            solutions_found = self.solutions,
+            solutions_submitted = self.submitted,
+            best_difficulty = self.best_difficulty,
+            "Session summary"
+        );
+    }
+}
+
+impl Default for SessionSummary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A point-in-time view of the running miner, served as JSON by `serve_status`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatusSnapshot {
+    pub paused: bool,
+    pub min_difficulty: u32,
+    pub round: u64,
+    pub round_started_at: Option<i64>,
+    pub round_deadline: Option<i64>,
+    pub round_progress: f64,
+    pub best_difficulty: u32,
+    pub challenges: Vec<ChallengeStatus>,
+    pub session: Option<SessionSummary>,
+    pub pool_connected: bool,
+    pub recent_signatures: VecDeque<String>,
+    /// Hashes per second of each hashing thread, indexed by core id.
+    pub core_hashrates: Vec<u64>,
+    /// Hashes found at each difficulty this session, indexed by difficulty.
+    pub difficulty_histogram: Vec<u64>,
+    #[serde(skip)]
+    pub log_lines: VecDeque<String>,
+}
+
+/// Live state of a running miner, shared by the mining loop, transaction sender and pool client.
+///
+/// Counters written by hashing threads are atomics so that hashing never waits on `state`.
+pub struct MinerStatus {
+    paused: AtomicBool,
+    tui_active: AtomicBool,
+    min_difficulty: AtomicU32,
+    min_difficulty_floor: AtomicU32,
+    round_best_difficulty: AtomicU32,
+    core_hashrates: OnceLock<Vec<AtomicU64>>,
+    difficulty_histogram: [AtomicU64; DIFFICULTY_BUCKETS],
+    state: Mutex<StatusSnapshot>,
+}
+
+impl Default for MinerStatus {
+    fn default() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            tui_active: AtomicBool::new(false),
+            min_difficulty: AtomicU32::new(0),
+            min_difficulty_floor: AtomicU32::new(0),
+            round_best_difficulty: AtomicU32::new(0),
+            core_hashrates: OnceLock::new(),
+            difficulty_histogram: std::array::from_fn(|_| AtomicU64::new(0)),
+            state: Mutex::new(StatusSnapshot::default()),
+        }
+    }
+}
+
+impl MinerStatus {
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub fn min_difficulty(&self) -> u32 {
+        self.min_difficulty.load(Ordering::Relaxed)
+    }
+
+    pub fn set_min_difficulty(&self, min_difficulty: u32) {
+        self.min_difficulty.store(min_difficulty, Ordering::Relaxed);
+    }
+
+    /// The lowest min difficulty the program accepts, from the on-chain config.
+    pub fn min_difficulty_floor(&self) -> u32 {
+        self.min_difficulty_floor.load(Ordering::Relaxed)
+    }
+
+    pub fn set_min_difficulty_floor(&self, min_difficulty: u32) {
+        self.min_difficulty_floor
+            .store(min_difficulty, Ordering::Relaxed);
+    }
+
+    pub fn is_tui_active(&self) -> bool {
+        self.tui_active.load(Ordering::Relaxed)
+    }
+
+    pub fn set_tui_active(&self, active: bool) {
+        self.tui_active.store(active, Ordering::Relaxed);
+    }
+
+    /// Records the challenges of a new round.
+    pub fn start_round(&self, round: u64, challenges: Vec<ChallengeStatus>) {
+        let now = chrono::Utc::now().timestamp();
+        let deadline = challenges.iter().map(|c| c.time_limit).max().unwrap_or(0);
+        self.round_best_difficulty.store(0, Ordering::Relaxed);
+        let mut state = self.state.lock().unwrap();
+        state.round = round;
+        state.round_started_at = Some(now);
+        state.round_deadline = Some(now + deadline as i64);
+        state.challenges = challenges;
+    }
+
+    /// Raises the best difficulty of the current round. Called from hashing threads.
+    pub fn record_difficulty(&self, difficulty: u32) {
+        self.round_best_difficulty
+            .fetch_max(difficulty, Ordering::Relaxed);
+    }
+
+    /// Sizes the per-core hashrates for the session's hashing threads. Only the first
+    /// call has an effect.
+    pub fn init_cores(&self, cores: usize) {
+        let _ = self
+            .core_hashrates
+            .set((0..cores).map(|_| AtomicU64::new(0)).collect());
+    }
+
+    /// Records the hashrate of one hashing thread. Called from hashing threads.
+    pub fn set_core_hashrate(&self, core: usize, hashrate: u64) {
+        if let Some(slot) = self.core_hashrates.get().and_then(|rates| rates.get(core)) {
+            slot.store(hashrate, Ordering::Relaxed);
+        }
+    }
+
+    /// Adds a thread's difficulty counts to the session histogram. Called from hashing threads.
+    pub fn add_histogram(&self, histogram: &[u64]) {
+        for (total, count) in self.difficulty_histogram.iter().zip(histogram) {
+            if *count > 0 {
+                total.fetch_add(*count, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn add_log_line(&self, line: String) {
+        let mut state = self.state.lock().unwrap();
+        state.log_lines.push_front(line);
+        state.log_lines.truncate(RECENT_LOG_LINES);
+    }
+
+    pub fn set_session(&self, session: &SessionSummary) {
+        self.state.lock().unwrap().session = Some(session.clone());
+    }
+
+    pub fn set_pool_connected(&self, connected: bool) {
+        self.state.lock().unwrap().pool_connected = connected;
+    }
+
+    pub fn add_signature(&self, signature: String) {
+        let mut state = self.state.lock().unwrap();
+        state.recent_signatures.push_front(signature);
+        state.recent_signatures.truncate(RECENT_SIGNATURES);
+    }
+
+    pub fn snapshot(&self) -> StatusSnapshot {
+        let mut snapshot = self.state.lock().unwrap().clone();
+        snapshot.paused = self.is_paused();
+        snapshot.min_difficulty = self.min_difficulty();
+        snapshot.best_difficulty = self.round_best_difficulty.load(Ordering::Relaxed);
+        snapshot.core_hashrates = self
+            .core_hashrates
+            .get()
+            .map(|rates| {
+                rates
+                    .iter()
+                    .map(|rate| rate.load(Ordering::Relaxed))
+                    .collect()
+            })
+            .unwrap_or_default();
+        snapshot.difficulty_histogram = self
+            .difficulty_histogram
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .collect();
+        if let (Some(started_at), Some(deadline)) =
+            (snapshot.round_started_at, snapshot.round_deadline)
+        {
+            let now = chrono::Utc::now().timestamp();
+            let total = (deadline - started_at).max(1) as f64;
+            snapshot.round_progress = ((now - started_at) as f64 / total).clamp(0.0, 1.0);
+        }
+        snapshot
+    }
+}
+
+/// Returns the process-wide miner status.
+pub fn status() -> &'static MinerStatus {
+    static STATUS: OnceLock<MinerStatus> = OnceLock::new();
+    STATUS.get_or_init(MinerStatus::default)
+}
+
+/// Serves `GET /status`, `POST /pause` and `POST /resume` until the process exits.
+pub async fn serve_status(addr: SocketAddr) -> Result<(), std::io::Error> {
+    let app = Router::new()
+        .route("/status", get(|| async { Json(status().snapshot()) }))
+        .route(
+            "/pause",
+            post(|| async {
+                status().set_paused(true);
+                tracing::info!("Mining paused");
+                Json(status().snapshot())
+            }),
+        )
+        .route(
+            "/resume",
+            post(|| async {
+                status().set_paused(false);
+                tracing::info!("Mining resumed");
+                Json(status().snapshot())
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await
+}
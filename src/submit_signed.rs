@@ -0,0 +1,80 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use colored::*;
+use solana_sdk::{signature::Signature, transaction::Transaction};
+
+use crate::{args::SubmitSignedArgs, Miner};
+
+impl Miner {
+    pub async fn submit_signed(&self, args: SubmitSignedArgs) {
+        // Decode every partially signed copy of the tx
+        let mut txs = vec![];
+        for encoded in args.transactions.iter() {
+            match decode_transaction(encoded) {
+                Some(tx) => txs.push(tx),
+                None => {
+                    println!(
+                        "{} Failed to decode transaction: {}",
+                        "ERROR".bold().red(),
+                        encoded
+                    );
+                    return;
+                }
+            }
+        }
+
+        // Combine signatures
+        let mut tx = txs.remove(0);
+        for other in txs {
+            if other.message != tx.message {
+                println!(
+                    "{} Transactions have different messages and cannot be combined",
+                    "ERROR".bold().red()
+                );
+                return;
+            }
+            for (sig, other_sig) in tx.signatures.iter_mut().zip(other.signatures) {
+                if *sig == Signature::default() {
+                    *sig = other_sig;
+                }
+            }
+        }
+
+        // Confirm every required signature is present and valid
+        let missing: Vec<_> = tx
+            .message
+            .account_keys
+            .iter()
+            .zip(tx.signatures.iter())
+            .filter(|(_, sig)| **sig == Signature::default())
+            .map(|(key, _)| key.to_string())
+            .collect();
+        if !missing.is_empty() {
+            println!(
+                "{} Missing signatures from: {}",
+                "ERROR".bold().red(),
+                missing.join(", ")
+            );
+            return;
+        }
+        if tx.verify().is_err() {
+            println!(
+                "{} Transaction has invalid signatures",
+                "ERROR".bold().red()
+            );
+            return;
+        }
+
+        // Broadcast as signed
+        self.send_and_confirm_tx(tx, None, &[], false).await.ok();
+    }
+}
+
+fn decode_transaction(encoded: &str) -> Option<Transaction> {
+    // Some base64 strings are also valid base58, so fall back on any failure
+    let deserialize = |bytes: Vec<u8>| bincode::deserialize::<Transaction>(&bytes).ok();
+    bs58::decode(encoded)
+        .into_vec()
+        .ok()
+        .and_then(deserialize)
+        .or_else(|| STANDARD.decode(encoded).ok().and_then(deserialize))
+}
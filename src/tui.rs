@@ -0,0 +1,270 @@
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{BarChart, Block, Borders, Gauge, List, ListItem, Paragraph},
+    Frame, Terminal,
+};
+
+use crate::status::{status, StatusSnapshot};
+
+/// How often the dashboard redraws and polls for key presses.
+const TICK: Duration = Duration::from_millis(250);
+
+/// Runs the mining dashboard on the current thread until `shutdown` is set.
+///
+/// Keys: `p` pauses or resumes hashing, `+` and `-` change the minimum difficulty for the next
+/// round, down to the on-chain minimum, and `q` or Ctrl-C stops mining gracefully. A second
+/// Ctrl-C restores the terminal and exits immediately.
+pub fn run_tui(shutdown: Arc<AtomicBool>) -> io::Result<()> {
+    // Capture logs before the screen is taken over, so none are drawn over it
+    status().set_tui_active(true);
+    let _guard = TerminalGuard;
+    install_panic_hook();
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    draw_loop(&shutdown)
+}
+
+/// Restores the terminal when the dashboard stops, however it stops.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Restores the terminal before a panic on any thread is reported, so the message is
+/// readable and the shell is usable afterwards.
+fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        previous(info);
+    }));
+}
+
+/// Leaves raw mode and the alternate screen if the dashboard is shown. Safe to call
+/// before exiting the process from any thread.
+pub fn restore_terminal() {
+    if status().is_tui_active() {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        status().set_tui_active(false);
+    }
+}
+
+fn draw_loop(shutdown: &AtomicBool) -> io::Result<()> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    while !shutdown.load(Ordering::Relaxed) {
+        let snapshot = status().snapshot();
+        terminal.draw(|frame| draw(frame, &snapshot))?;
+
+        // Handle keys
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if shutdown.swap(true, Ordering::Relaxed) {
+                    restore_terminal();
+                    std::process::exit(130);
+                }
+            }
+            KeyCode::Char('q') => shutdown.store(true, Ordering::Relaxed),
+            KeyCode::Char('p') => status().set_paused(!status().is_paused()),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                status().set_min_difficulty(status().min_difficulty().saturating_add(1))
+            }
+            KeyCode::Char('-') => status().set_min_difficulty(
+                status()
+                    .min_difficulty()
+                    .saturating_sub(1)
+                    .max(status().min_difficulty_floor()),
+            ),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, snapshot: &StatusSnapshot) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Min(8),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[2]);
+    let feeds = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[3]);
+
+    // Summary
+    let hashrate: u64 = snapshot.core_hashrates.iter().sum();
+    let session = snapshot.session.clone().unwrap_or_default();
+    let summary = vec![
+        Line::from(format!(
+            "{} | Round {} | Hashrate {} H/s | Best {} | Target {} | Pool {}",
+            if snapshot.paused { "PAUSED" } else { "MINING" },
+            snapshot.round,
+            hashrate,
+            snapshot.best_difficulty,
+            snapshot.min_difficulty,
+            if snapshot.pool_connected {
+                "connected"
+            } else {
+                "disconnected"
+            },
+        )),
+        Line::from(format!(
+            "Session: {} rounds | {} solutions | {} submitted | best difficulty {}",
+            session.rounds, session.solutions, session.submitted, session.best_difficulty
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(summary).block(Block::default().borders(Borders::ALL).title("ORE")),
+        rows[0],
+    );
+
+    // Round countdown
+    let remaining = snapshot
+        .round_deadline
+        .map(|deadline| (deadline - chrono::Utc::now().timestamp()).max(0))
+        .unwrap_or(0);
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title("Round"))
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio(snapshot.round_progress)
+            .label(format!("{}s to cutoff", remaining)),
+        rows[1],
+    );
+
+    // Per-core hashrate
+    let core_labels: Vec<String> = (0..snapshot.core_hashrates.len())
+        .map(|core| core.to_string())
+        .collect();
+    let core_data: Vec<(&str, u64)> = core_labels
+        .iter()
+        .map(String::as_str)
+        .zip(snapshot.core_hashrates.iter().copied())
+        .collect();
+    frame.render_widget(
+        BarChart::default()
+            .block(Block::default().borders(Borders::ALL).title("H/s per core"))
+            .bar_width(5)
+            .data(&core_data),
+        charts[0],
+    );
+
+    // Difficulty histogram
+    let first = snapshot
+        .difficulty_histogram
+        .iter()
+        .position(|count| *count > 0)
+        .unwrap_or(0);
+    let last = snapshot
+        .difficulty_histogram
+        .iter()
+        .rposition(|count| *count > 0)
+        .map_or(0, |i| i + 1);
+    let difficulty_labels: Vec<String> = (first..last).map(|d| d.to_string()).collect();
+    let difficulty_data: Vec<(&str, u64)> = difficulty_labels
+        .iter()
+        .map(String::as_str)
+        .zip(snapshot.difficulty_histogram[first..last].iter().copied())
+        .collect();
+    frame.render_widget(
+        BarChart::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Hashes by difficulty"),
+            )
+            .bar_width(3)
+            .data(&difficulty_data),
+        charts[1],
+    );
+
+    // Recent submissions and log
+    let signatures: Vec<ListItem> = snapshot
+        .recent_signatures
+        .iter()
+        .map(|signature| ListItem::new(signature.clone()))
+        .collect();
+    frame.render_widget(
+        List::new(signatures).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Recent transactions"),
+        ),
+        feeds[0],
+    );
+    let log_lines: Vec<ListItem> = snapshot
+        .log_lines
+        .iter()
+        .map(|line| ListItem::new(line.clone()))
+        .collect();
+    frame.render_widget(
+        List::new(log_lines).block(Block::default().borders(Borders::ALL).title("Log")),
+        feeds[1],
+    );
+
+    // Keys
+    frame.render_widget(
+        Paragraph::new("p pause/resume | +/- target difficulty | q quit"),
+        rows[4],
+    );
+}
+
+/// Sends log lines to the dashboard while it is shown and to stderr otherwise.
+pub struct TuiLogWriter;
+
+impl Write for TuiLogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if status().is_tui_active() {
+            let line = String::from_utf8_lossy(buf).trim_end().to_string();
+            if !line.is_empty() {
+                status().add_log_line(line);
+            }
+            Ok(buf.len())
+        } else {
+            io::stderr().write(buf)
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
@@ -1,9 +1,10 @@
 use colored::*;
-use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use solana_program::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
 use spl_token::amount_to_ui_amount;
 
 use crate::{
-    cu_limits::CU_LIMIT_UPGRADE,
+    cu_limits::{CU_LIMIT_ATA, CU_LIMIT_UPGRADE},
     send_and_confirm::ComputeBudget,
     utils::{amount_f64_to_u64_v1, ask_confirm},
     Miner, UpgradeArgs,
@@ -12,7 +13,8 @@ use crate::{
 impl Miner {
     pub async fn upgrade(&self, args: UpgradeArgs) {
         let signer = &self.signer();
-        let beneficiary = self.get_or_initialize_ata().await;
+        let mut ixs = vec![];
+        let beneficiary = self.get_or_initialize_ata(&mut ixs).await;
         let (sender, sender_balance) = self.get_ata_v1().await;
 
         let amount_f64 = match args.amount {
@@ -28,19 +30,28 @@ impl Miner {
         let amount = amount_f64_to_u64_v1(amount_f64);
         let amount_ui = amount_to_ui_amount(amount, ore_api::consts::TOKEN_DECIMALS_V1);
 
-        if !ask_confirm(
-            format!(
-                "\n You are about to upgrade {}. \n\nAre you sure you want to continue? [Y/n]",
-                format!("{} ORE", amount_ui).bold(),
+        if !self.dry_run
+            && !ask_confirm(
+                format!(
+                    "\n You are about to upgrade {}. \n\nAre you sure you want to continue?",
+                    format!("{} ORE", amount_ui).bold(),
+                )
+                .as_str(),
+                self.assume_yes,
             )
-            .as_str(),
-        ) {
+        {
             return;
         }
 
-        let ix = ore_api::instruction::upgrade(signer.pubkey(), beneficiary, sender, amount);
+        let cu_limit = CU_LIMIT_UPGRADE + CU_LIMIT_ATA * ixs.len() as u32;
+        ixs.push(ore_api::instruction::upgrade(
+            signer.pubkey(),
+            beneficiary,
+            sender,
+            amount,
+        ));
         match self
-            .send_and_confirm(&[ix], ComputeBudget::Fixed(CU_LIMIT_UPGRADE), false)
+            .send_and_confirm(&ixs, ComputeBudget::Fixed(cu_limit), false)
             .await
         {
             Ok(_sig) => {}
@@ -85,7 +96,7 @@ impl Miner {
         (token_account_pubkey_v1, balance)
     }
 
-    async fn get_or_initialize_ata(&self) -> Pubkey {
+    async fn get_or_initialize_ata(&self, ixs: &mut Vec<Instruction>) -> Pubkey {
         // Initialize client
         let signer = self.signer();
         let client = self.rpc_client.clone();
@@ -96,18 +107,20 @@ impl Miner {
             &ore_api::consts::MINT_ADDRESS,
         );
 
-        // Check if ata already exists or init
-        if let Err(_err) = client.get_token_account(&token_account_pubkey).await {
+        // Check if ata already exists or init in the same transaction
+        if !matches!(
+            client.get_token_account(&token_account_pubkey).await,
+            Ok(Some(_))
+        ) {
             println!("Initializing v2 token account...");
-            let ix = spl_associated_token_account::instruction::create_associated_token_account(
-                &signer.pubkey(),
-                &signer.pubkey(),
-                &ore_api::consts::MINT_ADDRESS,
-                &spl_token::id(),
+            ixs.push(
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &signer.pubkey(),
+                    &signer.pubkey(),
+                    &ore_api::consts::MINT_ADDRESS,
+                    &spl_token::id(),
+                ),
             );
-            self.send_and_confirm(&[ix], ComputeBudget::Dynamic, false)
-                .await
-                .ok();
         }
 
         // Return token account address
@@ -1,3 +1,9 @@
+use ore_api::{
+    consts::{BUS_ADDRESSES, BUS_COUNT},
+    state::Bus,
+};
+use ore_utils::AccountDeserialize;
+use rand::{seq::SliceRandom, Rng};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_program::{
     instruction::Instruction,
@@ -8,10 +14,16 @@ use solana_sdk::{
     signature::{Keypair, Signature, Signer},
     transaction::Transaction,
 };
-use std::str::FromStr;
+use std::{
+    io::IsTerminal,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 pub struct ProofState {
     pub challenge: [u8; 32],
+    pub last_hash_at: i64,
     pub last_reset_at: i64,
     pub min_difficulty: u32,
     pub base_reward_rate: u64,
@@ -21,6 +33,7 @@ pub struct ProofState {
 pub async fn get_config(client: &RpcClient) -> Result<ProofState, Box<dyn std::error::Error>> {
     Ok(ProofState {
         challenge: [0; 32],
+        last_hash_at: 0,
         last_reset_at: 0,
         min_difficulty: 16,
         base_reward_rate: 1000,
@@ -35,6 +48,7 @@ pub async fn get_updated_proof_with_authority(
 ) -> Result<ProofState, Box<dyn std::error::Error>> {
     Ok(ProofState {
         challenge: [0; 32],
+        last_hash_at: 0,
         last_reset_at: 0,
         min_difficulty: 16,
         base_reward_rate: 1000,
@@ -60,21 +74,69 @@ pub fn create_mine_ix(
     }
 }
 
-pub async fn find_available_bus(client: &RpcClient) -> Result<Pubkey, Box<dyn std::error::Error>> {
-    let bus_addresses = [
-        "BUS1111111111111111111111111111111111111111",
-        "BUS2222222222222222222222222222222222222222",
-        "BUS3333333333333333333333333333333333333333",
-    ];
+/// How long fetched bus balances are reused before they are refreshed.
+const BUS_CACHE_TTL: Duration = Duration::from_secs(30);
 
-    for addr in bus_addresses {
-        let pubkey = Pubkey::from_str(addr)?;
-        if let Ok(_) = client.get_account(&pubkey).await {
-            return Ok(pubkey);
-        }
+/// The remaining rewards of each bus, by address.
+type BusBalances = Vec<(Pubkey, u64)>;
+
+/// The last fetched reward balance of each bus.
+static BUS_CACHE: Mutex<Option<(Instant, BusBalances)>> = Mutex::new(None);
+
+/// Returns the bus with the most remaining rewards, picking randomly among ties so
+/// miners don't all contend for the same bus. Bus balances are cached for a short time
+/// and reused if a refresh fails. Falls back to a random bus if none were ever fetched.
+pub async fn find_bus(client: &RpcClient) -> Pubkey {
+    let balances = fetch_bus_balances(client).await.unwrap_or_default();
+    select_bus(&balances, &mut rand::thread_rng())
+}
+
+/// Picks randomly among the busses with the most rewards, or any bus if `balances` is empty.
+fn select_bus<R: Rng + ?Sized>(balances: &[(Pubkey, u64)], rng: &mut R) -> Pubkey {
+    let top_balance = balances
+        .iter()
+        .map(|(_, rewards)| *rewards)
+        .max()
+        .unwrap_or(0);
+    let top_busses: Vec<Pubkey> = balances
+        .iter()
+        .filter(|(_, rewards)| *rewards == top_balance)
+        .map(|(address, _)| *address)
+        .collect();
+    match top_busses.choose(rng) {
+        Some(address) => *address,
+        None => BUS_ADDRESSES[rng.gen_range(0..BUS_COUNT)],
     }
+}
 
-    Err("No available bus found".into())
+async fn fetch_bus_balances(client: &RpcClient) -> Option<BusBalances> {
+    let mut cache = BUS_CACHE.lock().unwrap().clone();
+    if let Some((fetched_at, balances)) = &cache {
+        if fetched_at.elapsed() < BUS_CACHE_TTL {
+            return Some(balances.clone());
+        }
+    }
+    match client.get_multiple_accounts(&BUS_ADDRESSES).await {
+        Ok(accounts) => {
+            let balances: BusBalances = accounts
+                .into_iter()
+                .flatten()
+                .filter_map(|account| {
+                    Bus::try_from_bytes(&account.data)
+                        .ok()
+                        .and_then(|bus| {
+                            Some((*BUS_ADDRESSES.get(bus.id as usize)?, bus.rewards))
+                        })
+                })
+                .collect();
+            if !balances.is_empty() {
+                cache = Some((Instant::now(), balances));
+                *BUS_CACHE.lock().unwrap() = cache.clone();
+            }
+        }
+        Err(err) => tracing::warn!("Failed to fetch bus accounts: {}", err),
+    }
+    cache.map(|(_, balances)| balances)
 }
 
 pub async fn send_and_confirm_transaction(
@@ -88,10 +150,6 @@ pub async fn send_and_confirm_transaction(
     Ok(signature)
 }
 
-pub fn amount_u64_to_string(amount: u64) -> String {
-    format!("{}", amount)
-}
-
 pub fn amount_f64_to_u64(amount: f64) -> u64 {
     (amount * 1_000_000_000.0) as u64
 }
@@ -100,11 +158,36 @@ pub fn amount_f64_to_u64_v1(amount: f64) -> u64 {
     (amount * 1_000_000_000.0) as u64
 }
 
-pub fn ask_confirm(prompt: &str) -> bool {
+/// Returns the reward multiplier earned by a stake, which scales linearly up to 2x
+/// at the network's top stake balance.
+pub fn stake_multiplier(balance: u64, top_balance: u64) -> f64 {
+    if top_balance == 0 {
+        return 1.0;
+    }
+    1.0 + (balance.min(top_balance) as f64 / top_balance as f64)
+}
+
+pub fn ask_confirm(prompt: &str, assume_yes: bool) -> bool {
     println!("{} [y/N]", prompt);
+
+    // Skip the prompt if confirmation was given up front
+    if assume_yes {
+        println!("y (--yes)");
+        return true;
+    }
+
+    // Refuse to block on a non-interactive stdin
+    if !std::io::stdin().is_terminal() {
+        eprintln!("error: Cannot prompt for confirmation because stdin is not a terminal. Pass --yes to confirm non-interactively.");
+        std::process::exit(1);
+    }
+
+    // Anything other than an explicit yes is treated as no
     let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input.trim().to_lowercase() == "y"
+    if std::io::stdin().read_line(&mut input).is_err() {
+        return false;
+    }
+    matches!(input.trim().to_lowercase().as_str(), "y" | "yes")
 }
 
 pub fn proof_pubkey(index: u64) -> Pubkey {
@@ -114,6 +197,7 @@ pub fn proof_pubkey(index: u64) -> Pubkey {
 pub async fn get_proof(client: &RpcClient, index: u64) -> Result<ProofState, Box<dyn std::error::Error>> {
     Ok(ProofState {
         challenge: [0; 32],
+        last_hash_at: 0,
         last_reset_at: 0,
         min_difficulty: 16,
         base_reward_rate: 1000,
@@ -128,9 +212,76 @@ pub async fn get_proof_with_authority(
 ) -> Result<ProofState, Box<dyn std::error::Error>> {
     Ok(ProofState {
         challenge: [0; 32],
+        last_hash_at: 0,
         last_reset_at: 0,
         min_difficulty: 16,
         base_reward_rate: 1000,
         top_balance: 0,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn stake_multiplier_without_stake() {
+        assert_eq!(stake_multiplier(0, 1_000), 1.0);
+    }
+
+    #[test]
+    fn stake_multiplier_scales_linearly() {
+        assert_eq!(stake_multiplier(250, 1_000), 1.25);
+        assert_eq!(stake_multiplier(500, 1_000), 1.5);
+    }
+
+    #[test]
+    fn stake_multiplier_caps_at_top_balance() {
+        assert_eq!(stake_multiplier(1_000, 1_000), 2.0);
+        assert_eq!(stake_multiplier(5_000, 1_000), 2.0);
+    }
+
+    #[test]
+    fn stake_multiplier_without_top_balance() {
+        assert_eq!(stake_multiplier(1_000, 0), 1.0);
+    }
+
+    #[test]
+    fn select_bus_picks_most_rewards() {
+        let balances = [
+            (BUS_ADDRESSES[0], 10),
+            (BUS_ADDRESSES[1], 30),
+            (BUS_ADDRESSES[2], 20),
+        ];
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            assert_eq!(select_bus(&balances, &mut rng), BUS_ADDRESSES[1]);
+        }
+    }
+
+    #[test]
+    fn select_bus_breaks_ties_randomly() {
+        let balances = [
+            (BUS_ADDRESSES[0], 30),
+            (BUS_ADDRESSES[1], 10),
+            (BUS_ADDRESSES[2], 30),
+        ];
+        let mut rng = StdRng::seed_from_u64(0);
+        let picked: HashSet<Pubkey> = (0..100)
+            .map(|_| select_bus(&balances, &mut rng))
+            .collect();
+        assert_eq!(picked, HashSet::from([BUS_ADDRESSES[0], BUS_ADDRESSES[2]]));
+    }
+
+    #[test]
+    fn select_bus_falls_back_to_any_bus() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let picked: HashSet<Pubkey> = (0..1_000).map(|_| select_bus(&[], &mut rng)).collect();
+        assert!(picked.iter().all(|address| BUS_ADDRESSES.contains(address)));
+        assert!(picked.len() > 1);
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+    time::Duration,
+};
+
+use futures::future::join_all;
+use reqwest::Client;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+/// A mining event delivered to webhooks as a JSON object with an `event` field.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A round produced a new session best difficulty at or above the threshold.
+    BestDifficulty {
+        authority: String,
+        difficulty: u32,
+        hash: String,
+    },
+    /// Transactions failed this many times in a row.
+    TransactionFailureStreak { failures: u64, error: String },
+    /// The fee payer's balance fell below the configured level.
+    LowBalance {
+        address: String,
+        balance: f64,
+        threshold: f64,
+    },
+    /// The pool connection closed unexpectedly.
+    PoolDisconnected { url: String, error: Option<String> },
+    /// The pool rejected a submitted hash.
+    HashRejected { error: String },
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    #[serde(flatten)]
+    event: &'a WebhookEvent,
+    timestamp: i64,
+}
+
+struct Webhooks {
+    client: Client,
+    urls: Vec<String>,
+    difficulty_threshold: Option<u32>,
+    failure_streak: u64,
+    failures: AtomicU64,
+    deliveries: Mutex<Vec<JoinHandle<()>>>,
+}
+
+static WEBHOOKS: OnceLock<Webhooks> = OnceLock::new();
+
+/// Enables webhooks for the rest of the process. Events are dropped until this is called.
+pub fn configure_webhooks(
+    urls: Vec<String>,
+    difficulty_threshold: Option<u32>,
+    failure_streak: u64,
+) {
+    if urls.is_empty() {
+        return;
+    }
+    let _ = WEBHOOKS.set(Webhooks {
+        client: Client::new(),
+        urls,
+        difficulty_threshold,
+        failure_streak: failure_streak.max(1),
+        failures: AtomicU64::new(0),
+        deliveries: Mutex::new(vec![]),
+    });
+}
+
+/// Returns the difficulty at or above which new best difficulties are reported.
+pub fn difficulty_threshold() -> Option<u32> {
+    WEBHOOKS
+        .get()
+        .and_then(|webhooks| webhooks.difficulty_threshold)
+}
+
+/// Posts an event to every webhook in the background. Delivery failures are logged, and
+/// `flush_webhooks` waits for deliveries still in flight.
+pub fn notify(event: WebhookEvent) {
+    let Some(webhooks) = WEBHOOKS.get() else {
+        return;
+    };
+    let body = WebhookPayload {
+        event: &event,
+        timestamp: chrono::Utc::now().timestamp(),
+    };
+    let Ok(body) = serde_json::to_value(&body) else {
+        return;
+    };
+    let mut deliveries = webhooks.deliveries.lock().unwrap();
+    deliveries.retain(|delivery| !delivery.is_finished());
+    for url in webhooks.urls.iter() {
+        let request = webhooks.client.post(url).json(&body);
+        let url = url.clone();
+        deliveries.push(tokio::spawn(async move {
+            match request.send().await.and_then(|res| res.error_for_status()) {
+                Ok(_) => {}
+                Err(err) => tracing::warn!("Failed to deliver webhook to {}: {}", url, err),
+            }
+        }));
+    }
+}
+
+/// Waits up to `timeout` for webhook deliveries still in flight, so events sent just
+/// before shutdown are not lost.
+pub async fn flush_webhooks(timeout: Duration) {
+    let Some(webhooks) = WEBHOOKS.get() else {
+        return;
+    };
+    let deliveries = std::mem::take(&mut *webhooks.deliveries.lock().unwrap());
+    if tokio::time::timeout(timeout, join_all(deliveries))
+        .await
+        .is_err()
+    {
+        tracing::warn!("Timed out delivering webhooks");
+    }
+}
+
+/// Tracks consecutive transaction failures and reports each completed streak.
+pub fn record_transaction_result(error: Option<&str>) {
+    let Some(webhooks) = WEBHOOKS.get() else {
+        return;
+    };
+    match error {
+        None => webhooks.failures.store(0, Ordering::Relaxed),
+        Some(error) => {
+            let failures = webhooks.failures.fetch_add(1, Ordering::Relaxed) + 1;
+            if failures % webhooks.failure_streak == 0 {
+                notify(WebhookEvent::TransactionFailureStreak {
+                    failures,
+                    error: error.to_string(),
+                });
+            }
+        }
+    }
+}